@@ -0,0 +1,249 @@
+//! `#[derive(FromScanner)]` for atcoder-lib, enabled by its `derive` feature.
+//!
+//! Parses the item by hand instead of depending on syn, it only needs the
+//! struct name, generics and field types.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Scan every field in declaration order.
+///
+/// A parse error of a field is reported with the field name.
+#[proc_macro_derive(FromScanner)]
+pub fn derive_from_scanner(input: TokenStream) -> TokenStream {
+    match Struct::parse(input) {
+        Ok(s) => s.expand().parse().unwrap(),
+        Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+    }
+}
+
+struct Struct {
+    name: String,
+    // inside `<..>` as written, and just the parameter names
+    generics: String,
+    generic_args: String,
+    where_clause: String,
+    fields: Fields,
+}
+
+enum Fields {
+    Named(Vec<(String, String)>),
+    Unnamed(Vec<String>),
+    Unit,
+}
+
+impl Struct {
+    fn parse(input: TokenStream) -> Result<Self, String> {
+        let mut tokens = input.into_iter().peekable();
+
+        // attributes and visibility before the keyword
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => break,
+                Some(TokenTree::Ident(ident))
+                    if ident.to_string() == "enum" || ident.to_string() == "union" =>
+                {
+                    return Err("FromScanner can only be derived for structs".to_owned())
+                }
+                Some(_) => continue,
+                None => return Err("expected a struct".to_owned()),
+            }
+        }
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected a struct name".to_owned()),
+        };
+
+        let mut generics = vec![];
+        if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+            tokens.next();
+            let mut depth = 1;
+            for tt in tokens.by_ref() {
+                if let TokenTree::Punct(p) = &tt {
+                    match p.as_char() {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        _ => (),
+                    }
+                }
+                if depth == 0 {
+                    break;
+                }
+                generics.push(tt);
+            }
+        }
+        let generic_args = split_top_level(generics.clone())
+            .into_iter()
+            .map(|param| param_name(&param))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // the where clause is either before the braces or after the parens
+        let mut where_clause = vec![];
+        let mut fields = Fields::Unit;
+        for tt in tokens {
+            match tt {
+                TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+                    fields = Fields::Named(
+                        split_top_level(g.stream().into_iter().collect())
+                            .into_iter()
+                            .map(named_field)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis => {
+                    fields = Fields::Unnamed(
+                        split_top_level(g.stream().into_iter().collect())
+                            .into_iter()
+                            .map(|field| stringify(strip_attrs_and_vis(field)))
+                            .collect(),
+                    );
+                }
+                TokenTree::Punct(p) if p.as_char() == ';' => (),
+                TokenTree::Ident(ident) if ident.to_string() == "where" => (),
+                tt => where_clause.push(tt),
+            }
+        }
+
+        Ok(Struct {
+            name,
+            generics: stringify(generics),
+            generic_args,
+            where_clause: stringify(where_clause),
+            fields,
+        })
+    }
+
+    fn expand(&self) -> String {
+        let cio = "::atcoder_lib::contest::cio";
+        let scan = |ty: &str, field: &str| {
+            format!(
+                "<{ty} as {cio}::FromScanner<__R>>::try_from_scanner(s).map_err(|e| e.in_field({field:?}))?",
+                ty = ty,
+                cio = cio,
+                field = field,
+            )
+        };
+        let (types, body) = match &self.fields {
+            Fields::Named(fields) => (
+                fields.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>(),
+                format!(
+                    "Self {{ {} }}",
+                    fields
+                        .iter()
+                        .map(|(name, ty)| format!("{}: {}", name, scan(ty, name)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+            Fields::Unnamed(types) => (
+                types.clone(),
+                format!(
+                    "Self({})",
+                    types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, ty)| scan(ty, &i.to_string()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+            Fields::Unit => (vec![], "Self".to_owned()),
+        };
+
+        let mut bounds = vec!["__R: ::std::io::BufRead".to_owned()];
+        bounds.extend(
+            types
+                .iter()
+                .map(|ty| format!("{}: {}::FromScanner<__R>", ty, cio)),
+        );
+        if !self.where_clause.is_empty() {
+            bounds.push(self.where_clause.trim_end_matches(',').to_owned());
+        }
+        let generics = if self.generics.is_empty() {
+            "__R".to_owned()
+        } else {
+            format!("{}, __R", self.generics)
+        };
+
+        format!(
+            "impl<{generics}> {cio}::FromScanner<__R> for {name}<{args}> where {bounds} {{
+                fn try_from_scanner(s: &mut {cio}::Scanner<__R>) -> {cio}::Result<Self> {{
+                    Ok({body})
+                }}
+            }}",
+            generics = generics,
+            cio = cio,
+            name = self.name,
+            args = self.generic_args,
+            bounds = bounds.join(", "),
+            body = body,
+        )
+    }
+}
+
+/// Split on commas outside of `<..>`, groups already hide their own commas.
+fn split_top_level(tokens: Vec<TokenTree>) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![vec![]];
+    let mut depth = 0;
+    for tt in tokens {
+        if let TokenTree::Punct(p) = &tt {
+            match p.as_char() {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(vec![]);
+                    continue;
+                }
+                _ => (),
+            }
+        }
+        parts.last_mut().unwrap().push(tt);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// `T` of `T: Bound`, `'a` of `'a: 'b` and `N` of `const N: usize`.
+fn param_name(param: &[TokenTree]) -> String {
+    match param {
+        [TokenTree::Punct(p), TokenTree::Ident(lifetime), ..] if p.as_char() == '\'' => {
+            format!("'{}", lifetime)
+        }
+        [TokenTree::Ident(c), TokenTree::Ident(name), ..] if c.to_string() == "const" => {
+            name.to_string()
+        }
+        [TokenTree::Ident(name), ..] => name.to_string(),
+        _ => stringify(param.to_vec()),
+    }
+}
+
+fn strip_attrs_and_vis(field: Vec<TokenTree>) -> Vec<TokenTree> {
+    let mut rest = &field[..];
+    loop {
+        match rest {
+            [TokenTree::Punct(p), TokenTree::Group(_), tail @ ..] if p.as_char() == '#' => {
+                rest = tail
+            }
+            [TokenTree::Ident(vis), TokenTree::Group(g), tail @ ..]
+                if vis.to_string() == "pub" && g.delimiter() == Delimiter::Parenthesis =>
+            {
+                rest = tail
+            }
+            [TokenTree::Ident(vis), tail @ ..] if vis.to_string() == "pub" => rest = tail,
+            _ => return rest.to_vec(),
+        }
+    }
+}
+
+fn named_field(field: Vec<TokenTree>) -> Result<(String, String), String> {
+    match &strip_attrs_and_vis(field)[..] {
+        [TokenTree::Ident(name), TokenTree::Punct(colon), ty @ ..] if colon.as_char() == ':' => {
+            Ok((name.to_string(), stringify(ty.to_vec())))
+        }
+        _ => Err("unexpected field".to_owned()),
+    }
+}
+
+fn stringify(tokens: Vec<TokenTree>) -> String {
+    tokens.into_iter().collect::<TokenStream>().to_string()
+}
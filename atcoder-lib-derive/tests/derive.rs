@@ -0,0 +1,62 @@
+use atcoder_lib::contest::cio::{Error, FromScanner, Scanner, Usize1};
+
+#[derive(FromScanner, Debug, PartialEq)]
+struct Query {
+    kind: u8,
+    range: (Usize1, Usize1),
+    pub value: i64,
+}
+
+#[derive(FromScanner, Debug, PartialEq)]
+struct Point(i64, i64);
+
+#[derive(FromScanner, Debug, PartialEq)]
+struct Labeled<T, const N: usize>
+where
+    T: Copy,
+{
+    label: char,
+    values: [T; N],
+}
+
+#[derive(FromScanner, Debug, PartialEq)]
+struct Marker;
+
+#[test]
+fn derive_named_and_tuple_structs() {
+    let mut scanner = Scanner::from("1 2 3 -5\n2 1 1 7\n3 4\n");
+    assert_eq!(
+        scanner.scan::<Query>(),
+        Query {
+            kind: 1,
+            range: (Usize1(1), Usize1(2)),
+            value: -5
+        }
+    );
+    assert_eq!(scanner.scan_n::<Query>(1)[0].value, 7);
+    assert_eq!(scanner.scan::<(Point, Marker)>(), (Point(3, 4), Marker));
+}
+
+#[test]
+fn derive_generic_struct() {
+    let mut scanner = Scanner::from("x 1 2 3\n");
+    assert_eq!(
+        scanner.scan::<Labeled<u32, 3>>(),
+        Labeled {
+            label: 'x',
+            values: [1, 2, 3]
+        }
+    );
+}
+
+#[test]
+fn error_names_the_field() {
+    let mut scanner = Scanner::from("1 2 3 abc\n");
+    let err = Query::try_from_scanner(&mut scanner).unwrap_err();
+    assert!(matches!(err, Error::Parse { .. }));
+    assert!(err.to_string().contains("field `value`"), "{}", err);
+
+    let mut scanner = Scanner::from("4 y");
+    let err = Point::try_from_scanner(&mut scanner).unwrap_err();
+    assert!(err.to_string().contains("field `1`"), "{}", err);
+}
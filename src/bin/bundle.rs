@@ -0,0 +1,413 @@
+//! Bundle a solution and the library modules it uses into a single file.
+//!
+//! ```text
+//! cargo run --bin bundle -- path/to/solution.rs > main.rs
+//! ```
+//!
+//! `use atcoder_lib::...` paths in the solution keep working because the library is
+//! emitted as an inline `mod atcoder_lib` at the crate root.
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LIB_NAME: &str = "atcoder_lib";
+
+/// A module backed by its own file, `lib.rs` is the root with an empty path.
+struct FileModule {
+    path: Vec<String>,
+    source: String,
+    children: Vec<usize>,
+}
+
+struct Library {
+    modules: Vec<FileModule>,
+    /// `#[macro_export]` macros live at the crate root, paired with their defining module.
+    macros: Vec<(String, usize)>,
+}
+
+impl Library {
+    fn load(src: &Path) -> Self {
+        let mut lib = Library {
+            modules: vec![],
+            macros: vec![],
+        };
+        lib.load_module(src, src.join("lib.rs"), vec![]);
+        lib
+    }
+
+    fn load_module(&mut self, src: &Path, file: PathBuf, path: Vec<String>) -> usize {
+        let source = fs::read_to_string(&file)
+            .unwrap_or_else(|err| panic!("failed to read {}: {}", file.display(), err));
+        let source = strip(&source);
+        let id = self.modules.len();
+        self.macros
+            .extend(exported_macros(&source).into_iter().map(|name| (name, id)));
+        self.modules.push(FileModule {
+            path: path.clone(),
+            source: source.clone(),
+            children: vec![],
+        });
+
+        for name in mod_declarations(&source) {
+            let mut child_path = path.clone();
+            child_path.push(name);
+            let dir = child_path[..child_path.len() - 1]
+                .iter()
+                .fold(src.to_path_buf(), |dir, seg| dir.join(seg));
+            let flat = dir.join(format!("{}.rs", child_path.last().unwrap()));
+            let nested = dir.join(child_path.last().unwrap()).join("mod.rs");
+            let file = if flat.exists() { flat } else { nested };
+            let child = self.load_module(src, file, child_path);
+            self.modules[id].children.push(child);
+        }
+        id
+    }
+
+    /// Resolve a path from the library root to the deepest file module it goes through.
+    fn resolve(&self, path: &[String]) -> Option<usize> {
+        if let Some((_, module)) = path
+            .first()
+            .and_then(|head| self.macros.iter().find(|(name, _)| name == head))
+        {
+            return Some(*module);
+        }
+        let mut current = 0;
+        let mut rest = path;
+        while let Some((head, tail)) = rest.split_first() {
+            match self.modules[current]
+                .children
+                .iter()
+                .find(|&&c| self.modules[c].path.last() == Some(head))
+            {
+                Some(&child) => {
+                    current = child;
+                    rest = tail;
+                }
+                None => {
+                    // maybe an item re-exported from a child with `pub use child::...`
+                    if let Some(child) = self.reexported_from(current, head) {
+                        current = child;
+                    }
+                    break;
+                }
+            }
+        }
+        Some(current)
+    }
+
+    fn reexported_from(&self, module: usize, item: &str) -> Option<usize> {
+        let m = &self.modules[module];
+        m.source.lines().find_map(|line| {
+            let line = line.trim();
+            let rest = line.strip_prefix("pub use ")?;
+            let child_name = rest.split("::").next()?;
+            if !contains_ident(rest, item) {
+                return None;
+            }
+            m.children
+                .iter()
+                .copied()
+                .find(|&c| self.modules[c].path.last().map(String::as_str) == Some(child_name))
+        })
+    }
+
+    /// File modules referenced from `module` through `crate::` or `super::` paths.
+    fn dependencies(&self, module: usize) -> Vec<usize> {
+        let m = &self.modules[module];
+        let mut deps = vec![];
+        for path in paths_after(&m.source, "crate::") {
+            deps.extend(self.resolve(&path));
+        }
+        // `super` of a file module, inline submodules are approximated by their file
+        if let Some((_, parent)) = m.path.split_last() {
+            for path in paths_after(&m.source, "super::") {
+                let mut full = parent.to_vec();
+                full.extend(path);
+                deps.extend(self.resolve(&full));
+            }
+        }
+        deps
+    }
+}
+
+/// Remove `#[cfg(test)]` items and doc comments.
+fn strip(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(pos) = rest.find("#[cfg(test)]") {
+        out.push_str(&rest[..pos]);
+        rest = skip_item(&rest[pos + "#[cfg(test)]".len()..]);
+    }
+    out.push_str(rest);
+
+    out.lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            !line.starts_with("///") && !line.starts_with("//!")
+        })
+        .fold(String::new(), |mut acc, line| {
+            acc.push_str(line);
+            acc.push('\n');
+            acc
+        })
+}
+
+/// Skip one item, either terminated by `;` or by its closing brace.
+fn skip_item(s: &str) -> &str {
+    let bytes = s.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            // char literal, not a lifetime
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b'\'' if bytes.get(i + 1) == Some(&b'\\') => {
+                while i + 1 < bytes.len() && bytes[i + 1] != b'\'' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &s[i + 1..];
+                }
+            }
+            b';' if depth == 0 => return &s[i + 1..],
+            _ => (),
+        }
+        i += 1;
+    }
+    ""
+}
+
+/// Names of `mod name;` declarations pointing to other files.
+fn mod_declarations(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| mod_declaration(line.trim()))
+        .collect()
+}
+
+fn mod_declaration(line: &str) -> Option<String> {
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    let line = line.strip_prefix("pub(crate) ").unwrap_or(line);
+    let name = line.strip_prefix("mod ")?.strip_suffix(';')?;
+    Some(name.trim().to_string())
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn contains_ident(s: &str, ident: &str) -> bool {
+    s.match_indices(ident).any(|(i, _)| {
+        let before = s[..i].bytes().last().map_or(false, is_ident_byte);
+        let after = s[i + ident.len()..]
+            .bytes()
+            .next()
+            .map_or(false, is_ident_byte);
+        !before && !after
+    })
+}
+
+/// Every path following `prefix` at an identifier boundary, `use` groups expanded.
+fn paths_after(source: &str, prefix: &str) -> Vec<Vec<String>> {
+    let mut paths = vec![];
+    for (i, _) in source.match_indices(prefix) {
+        if source[..i].bytes().last().map_or(false, is_ident_byte) {
+            continue;
+        }
+        let mut rest = &source[i + prefix.len()..];
+        parse_use_tree(&mut rest, vec![], &mut paths);
+    }
+    paths
+}
+
+/// Parse `a::b::{c, d::e}` and push every leaf path.
+fn parse_use_tree(s: &mut &str, prefix: Vec<String>, paths: &mut Vec<Vec<String>>) {
+    *s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('{') {
+        *s = rest;
+        loop {
+            parse_use_tree(s, prefix.clone(), paths);
+            *s = s.trim_start();
+            if let Some(rest) = s.strip_prefix(',') {
+                *s = rest;
+            } else {
+                break;
+            }
+            if let Some(rest) = s.trim_start().strip_prefix('}') {
+                *s = rest;
+                return;
+            }
+        }
+        if let Some(rest) = s.strip_prefix('}') {
+            *s = rest;
+        }
+        paths.push(prefix);
+        return;
+    }
+
+    let len = s.bytes().take_while(|&b| is_ident_byte(b)).count();
+    let (ident, rest) = s.split_at(len);
+    *s = rest;
+    let mut path = prefix;
+    if !ident.is_empty() && ident != "self" {
+        path.push(ident.to_string());
+    }
+    match s.strip_prefix("::") {
+        Some(rest) if !ident.is_empty() => {
+            *s = rest;
+            parse_use_tree(s, path, paths);
+        }
+        _ => paths.push(path),
+    }
+}
+
+/// Names of `#[macro_export] macro_rules! name`.
+fn exported_macros(source: &str) -> Vec<String> {
+    source
+        .match_indices("#[macro_export]")
+        .filter_map(|(i, _)| {
+            let rest = source[i..].split_once("macro_rules!")?.1.trim_start();
+            let len = rest.bytes().take_while(|&b| is_ident_byte(b)).count();
+            Some(rest[..len].to_string())
+        })
+        .collect()
+}
+
+/// Make absolute paths in library code point into the inline `atcoder_lib` module.
+///
+/// Exported macros stay at the crate root, so `crate::setup` is kept as is.
+fn rewrite_crate_paths(source: &str, macros: &[(String, usize)]) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut last = 0;
+    for (i, _) in source.match_indices("crate::") {
+        let before = source[..i].bytes().last();
+        if before.map_or(false, is_ident_byte) {
+            continue;
+        }
+        let after = &source[i + "crate::".len()..];
+        let len = after.bytes().take_while(|&b| is_ident_byte(b)).count();
+        if macros.iter().any(|(name, _)| name == &after[..len]) {
+            continue;
+        }
+        out.push_str(&source[last..i]);
+        out.push_str("crate::");
+        out.push_str(LIB_NAME);
+        out.push_str("::");
+        last = i + "crate::".len();
+    }
+    out.push_str(&source[last..]);
+    out
+}
+
+fn emit(lib: &Library, module: usize, needed: &BTreeSet<usize>, out: &mut String) {
+    let m = &lib.modules[module];
+    let excluded = m
+        .children
+        .iter()
+        .filter(|c| !needed.contains(c))
+        .filter_map(|&c| lib.modules[c].path.last().cloned())
+        .collect::<Vec<_>>();
+
+    let body = m
+        .source
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            if mod_declaration(line).is_some() {
+                return false;
+            }
+            let reexport = line.strip_prefix("pub use ").or_else(|| line.strip_prefix("use "));
+            !matches!(reexport, Some(rest) if excluded.iter().any(|name| rest.starts_with(&format!("{}::", name))))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    out.push_str(&rewrite_crate_paths(&body, &lib.macros));
+    out.push('\n');
+
+    for &child in m.children.iter().filter(|c| needed.contains(c)) {
+        out.push_str(&format!(
+            "pub mod {} {{\n",
+            lib.modules[child].path.last().unwrap()
+        ));
+        emit(lib, child, needed, out);
+        out.push_str("}\n");
+    }
+}
+
+fn bundle(solution: &str, src: &Path) -> String {
+    let lib = Library::load(src);
+
+    let mut stack = paths_after(solution, &format!("{}::", LIB_NAME))
+        .iter()
+        .filter_map(|path| lib.resolve(path))
+        .collect::<Vec<_>>();
+    let mut needed = BTreeSet::new();
+    while let Some(m) = stack.pop() {
+        if !needed.insert(m) {
+            continue;
+        }
+        stack.extend(lib.dependencies(m));
+        // ancestors host the module
+        let path = &lib.modules[m].path;
+        if !path.is_empty() {
+            stack.extend(lib.resolve(&path[..path.len() - 1]));
+        }
+    }
+
+    // exported macros are already at the crate root in the bundle
+    let solution = solution
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            !lib.macros
+                .iter()
+                .any(|(name, _)| line == format!("use {}::{};", LIB_NAME, name))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut out = String::new();
+    out.push_str(solution.trim_end());
+    out.push_str("\n\n#[allow(dead_code, unused_imports, unused_macros)]\n");
+    out.push_str(&format!("pub mod {} {{\n", LIB_NAME));
+    for (name, module) in &lib.macros {
+        if needed.contains(module) {
+            out.push_str(&format!("pub use crate::{};\n", name));
+        }
+    }
+    if !needed.is_empty() {
+        emit(&lib, 0, &needed, &mut out);
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .expect("usage: bundle <path/to/solution.rs>");
+    let solution =
+        fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {}: {}", path, err));
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+    print!("{}", bundle(&solution, &src));
+}
@@ -0,0 +1,193 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// Non-negative integer of arbitrary size.
+///
+/// Deliberately small: decimal conversion, `+`, `-`, `*` (schoolbook),
+/// comparison and division by a `u64`.
+/// Limbs are base `10^9` little endian without trailing zero limbs, so zero is empty.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns `(self / d, self % d)`.
+    pub fn div_rem_u64(&self, d: u64) -> (BigUint, u64) {
+        assert!(d != 0, "division by zero");
+        let mut limbs = vec![0; self.limbs.len()];
+        let mut rem = 0u128;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let cur = rem * BASE as u128 + limb as u128;
+            limbs[i] = (cur / d as u128) as u32;
+            rem = cur % d as u128;
+        }
+        (BigUint { limbs }.normalized(), rem as u64)
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(v: u64) -> Self {
+        BigUint::from(v as u128)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut v: u128) -> Self {
+        let mut limbs = Vec::new();
+        while v > 0 {
+            limbs.push((v % BASE as u128) as u32);
+            v /= BASE as u128;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigUintError);
+        }
+        let limbs = s
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| chunk.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u32))
+            .collect();
+        Ok(BigUint { limbs }.normalized())
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((last, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        write!(f, "{}", last)?;
+        for limb in rest.iter().rev() {
+            write!(f, "{:09}", limb)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> BigUint {
+        let n = self.limbs.len().max(rhs.limbs.len());
+        let mut limbs = Vec::with_capacity(n + 1);
+        let mut carry = 0;
+        for i in 0..n {
+            let a = self.limbs.get(i).copied().unwrap_or(0) as u64;
+            let b = rhs.limbs.get(i).copied().unwrap_or(0) as u64;
+            let s = a + b + carry;
+            limbs.push((s % BASE) as u32);
+            carry = s / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+        BigUint { limbs }
+    }
+}
+
+/// Panics if `rhs > self`.
+impl<'a> Sub<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        assert!(self >= rhs, "BigUint subtraction underflow");
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0;
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let b = rhs.limbs.get(i).copied().unwrap_or(0) as i64 + borrow;
+            let mut d = a as i64 - b;
+            borrow = 0;
+            if d < 0 {
+                d += BASE as i64;
+                borrow = 1;
+            }
+            limbs.push(d as u32);
+        }
+        BigUint { limbs }.normalized()
+    }
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        if self.is_zero() || rhs.is_zero() {
+            return BigUint::zero();
+        }
+        let mut acc = vec![0u64; self.limbs.len() + rhs.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in rhs.limbs.iter().enumerate() {
+                let cur = acc[i + j] + a as u64 * b as u64 + carry;
+                acc[i + j] = cur % BASE;
+                carry = cur / BASE;
+            }
+            acc[i + rhs.limbs.len()] += carry;
+        }
+        BigUint {
+            limbs: acc.into_iter().map(|limb| limb as u32).collect(),
+        }
+        .normalized()
+    }
+}
+
+macro_rules! impl_owned_op {
+    ($tr:ident, $f:ident) => {
+        impl $tr for BigUint {
+            type Output = BigUint;
+
+            fn $f(self, rhs: BigUint) -> BigUint {
+                (&self).$f(&rhs)
+            }
+        }
+    };
+}
+impl_owned_op!(Add, add);
+impl_owned_op!(Sub, sub);
+impl_owned_op!(Mul, mul);
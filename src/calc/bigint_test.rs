@@ -0,0 +1,90 @@
+use super::bigint::{BigUint, ParseBigUintError};
+
+fn big(s: &str) -> BigUint {
+    s.parse().unwrap()
+}
+
+#[test]
+fn bigint_parse_display() {
+    assert_eq!(big("0").to_string(), "0");
+    assert_eq!(big("0000").to_string(), "0");
+    assert_eq!(big("000123").to_string(), "123");
+    assert_eq!(big("1000000000").to_string(), "1000000000");
+    assert_eq!(
+        big("1000000000000000001").to_string(),
+        "1000000000000000001"
+    );
+    assert_eq!(big("007"), BigUint::from(7u64));
+    assert!(big("0").is_zero());
+    assert_eq!("".parse::<BigUint>(), Err(ParseBigUintError));
+    assert_eq!("12a".parse::<BigUint>(), Err(ParseBigUintError));
+}
+
+#[test]
+fn bigint_against_u128() {
+    let values: [u128; 8] = [
+        0,
+        1,
+        999_999_999,
+        1_000_000_000,
+        123_456_789_012_345_678,
+        u64::MAX as u128,
+        (u64::MAX as u128) * 3,
+        10u128.pow(30) + 7,
+    ];
+    for &a in &values {
+        for &b in &values {
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            assert_eq!((&x + &y).to_string(), (a + b).to_string());
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            if a >= b {
+                assert_eq!((&x - &y).to_string(), (a - b).to_string());
+            }
+            if let Some(p) = a.checked_mul(b) {
+                assert_eq!((&x * &y).to_string(), p.to_string());
+            }
+            if b != 0 && b <= u64::MAX as u128 {
+                let (q, r) = x.div_rem_u64(b as u64);
+                assert_eq!(q.to_string(), (a / b).to_string());
+                assert_eq!(r as u128, a % b);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn bigint_sub_underflow() {
+    let _ = big("1") - big("2");
+}
+
+#[test]
+fn bigint_mul_residues() {
+    let digits = |seed: u64| {
+        let mut x = seed;
+        (0..1000)
+            .map(|i| {
+                x = x
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let d = (x >> 60) % 10;
+                (if i == 0 { d % 9 + 1 } else { d } as u8 + b'0') as char
+            })
+            .collect::<String>()
+    };
+    let (a, b) = (digits(1), digits(2));
+    let residue = |s: &str, m: u64| {
+        s.bytes()
+            .fold(0u128, |acc, d| (acc * 10 + (d - b'0') as u128) % m as u128) as u64
+    };
+
+    let p = big(&a) * big(&b);
+    let digits = p.to_string();
+    assert!(digits.len() == 1999 || digits.len() == 2000);
+
+    for m in [9, (1u64 << 61) - 1] {
+        let expected = (residue(&a, m) as u128 * residue(&b, m) as u128 % m as u128) as u64;
+        assert_eq!(p.div_rem_u64(m).1, expected);
+        assert_eq!(residue(&digits, m), expected);
+    }
+}
@@ -0,0 +1,91 @@
+//! Zeta and Möbius transforms over the divisor lattice.
+//!
+//! All functions take 1-indexed slices, `a[0]` is ignored, and run in `O(n log log n)`.
+
+use std::ops::{AddAssign, Mul, SubAssign};
+
+fn primes_upto(n: usize) -> Vec<usize> {
+    let mut composite = vec![false; n + 1];
+    let mut primes = Vec::new();
+    for p in 2..=n {
+        if !composite[p] {
+            primes.push(p);
+            for m in (p * p..=n).step_by(p) {
+                composite[m] = true;
+            }
+        }
+    }
+    primes
+}
+
+/// `a[d] <- sum(a[k])` over multiples `k` of `d`.
+pub fn multiple_zeta<T: Copy + AddAssign>(a: &mut [T]) {
+    let n = a.len().saturating_sub(1);
+    for p in primes_upto(n) {
+        for i in (1..=n / p).rev() {
+            let v = a[i * p];
+            a[i] += v;
+        }
+    }
+}
+
+/// Inverse of [`multiple_zeta`].
+pub fn multiple_mobius<T: Copy + SubAssign>(a: &mut [T]) {
+    let n = a.len().saturating_sub(1);
+    for p in primes_upto(n) {
+        for i in 1..=n / p {
+            let v = a[i * p];
+            a[i] -= v;
+        }
+    }
+}
+
+/// `a[k] <- sum(a[d])` over divisors `d` of `k`.
+pub fn divisor_zeta<T: Copy + AddAssign>(a: &mut [T]) {
+    let n = a.len().saturating_sub(1);
+    for p in primes_upto(n) {
+        for i in 1..=n / p {
+            let v = a[i];
+            a[i * p] += v;
+        }
+    }
+}
+
+/// Inverse of [`divisor_zeta`].
+pub fn divisor_mobius<T: Copy + SubAssign>(a: &mut [T]) {
+    let n = a.len().saturating_sub(1);
+    for p in primes_upto(n) {
+        for i in (1..=n / p).rev() {
+            let v = a[i];
+            a[i * p] -= v;
+        }
+    }
+}
+
+/// `c[k] = sum(a[i] * b[j])` over `gcd(i, j) == k`.
+pub fn gcd_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + AddAssign + SubAssign + Mul<Output = T>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    multiple_zeta(&mut a);
+    multiple_zeta(&mut b);
+    let mut c = a.into_iter().zip(b).map(|(x, y)| x * y).collect::<Vec<_>>();
+    multiple_mobius(&mut c);
+    c
+}
+
+/// `c[k] = sum(a[i] * b[j])` over `lcm(i, j) == k`, for `k < a.len()`.
+pub fn lcm_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + AddAssign + SubAssign + Mul<Output = T>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    divisor_zeta(&mut a);
+    divisor_zeta(&mut b);
+    let mut c = a.into_iter().zip(b).map(|(x, y)| x * y).collect::<Vec<_>>();
+    divisor_mobius(&mut c);
+    c
+}
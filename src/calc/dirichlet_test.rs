@@ -0,0 +1,77 @@
+use super::dirichlet::*;
+use super::gcd::gcd;
+
+fn sample(n: usize) -> Vec<i64> {
+    (0..=n as i64).map(|i| (i * 7919 % 31) - 15).collect()
+}
+
+#[test]
+fn dirichlet_round_trip() {
+    let a = sample(100);
+
+    let mut b = a.clone();
+    multiple_zeta(&mut b);
+    assert_eq!(b[3], (1..=33).map(|k| a[3 * k]).sum::<i64>());
+    multiple_mobius(&mut b);
+    assert_eq!(&a[1..], &b[1..]);
+
+    let mut b = a.clone();
+    divisor_zeta(&mut b);
+    assert_eq!(b[12], a[1] + a[2] + a[3] + a[4] + a[6] + a[12]);
+    divisor_mobius(&mut b);
+    assert_eq!(&a[1..], &b[1..]);
+}
+
+#[test]
+fn dirichlet_convolution_brute() {
+    let n = 200;
+    let (a, b) = (sample(n), sample(n).into_iter().rev().collect::<Vec<_>>());
+
+    let mut gcd_c = vec![0; n + 1];
+    let mut lcm_c = vec![0; n + 1];
+    for i in 1..=n {
+        for j in 1..=n {
+            let g = gcd(i, j);
+            gcd_c[g] += a[i] * b[j];
+            if i / g * j <= n {
+                lcm_c[i / g * j] += a[i] * b[j];
+            }
+        }
+    }
+
+    assert_eq!(&gcd_convolution(&a, &b)[1..], &gcd_c[1..]);
+    assert_eq!(&lcm_convolution(&a, &b)[1..], &lcm_c[1..]);
+}
+
+#[test]
+fn dirichlet_coprime_pairs() {
+    let n = 1000;
+
+    // Möbius function by a linear sieve
+    let mut mu = vec![1i64; n + 1];
+    let mut composite = vec![false; n + 1];
+    let mut primes = vec![];
+    for i in 2..=n {
+        if !composite[i] {
+            primes.push(i);
+            mu[i] = -1;
+        }
+        for &p in &primes {
+            if i * p > n {
+                break;
+            }
+            composite[i * p] = true;
+            if i % p == 0 {
+                mu[i * p] = 0;
+                break;
+            }
+            mu[i * p] = -mu[i];
+        }
+    }
+    let expected = (1..=n)
+        .map(|d| mu[d] * ((n / d) as i64).pow(2))
+        .sum::<i64>();
+
+    let ones = vec![1i64; n + 1];
+    assert_eq!(gcd_convolution(&ones, &ones)[1], expected);
+}
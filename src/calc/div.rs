@@ -0,0 +1,65 @@
+use num::PrimInt;
+
+/// `a / b` rounded toward negative infinity.
+pub fn div_floor<T: PrimInt>(a: T, b: T) -> T {
+    assert!(!b.is_zero(), "division by zero");
+    let (q, r) = (a / b, a % b);
+    if !r.is_zero() && ((r < T::zero()) != (b < T::zero())) {
+        q - T::one()
+    } else {
+        q
+    }
+}
+
+/// `a / b` rounded toward positive infinity.
+pub fn div_ceil<T: PrimInt>(a: T, b: T) -> T {
+    assert!(!b.is_zero(), "division by zero");
+    let (q, r) = (a / b, a % b);
+    if !r.is_zero() && ((r < T::zero()) == (b < T::zero())) {
+        q + T::one()
+    } else {
+        q
+    }
+}
+
+/// Euclidean remainder, always in `0..|b|`.
+pub fn rem_floor<T: PrimInt>(a: T, b: T) -> T {
+    assert!(!b.is_zero(), "division by zero");
+    let r = a % b;
+    if r < T::zero() {
+        if b < T::zero() {
+            r - b
+        } else {
+            r + b
+        }
+    } else {
+        r
+    }
+}
+
+/// `a / b` rounded to the nearest integer, ties toward positive infinity (`round_div(-3, 2) == -1`).
+pub fn round_div<T: PrimInt>(a: T, b: T) -> T {
+    let q = div_floor(a, b);
+    // a == q * b + r where r has the sign of b and |r| < |b|
+    let r = a - q * b;
+    let (r, b) = if b < T::zero() {
+        (T::zero() - r, T::zero() - b)
+    } else {
+        (r, b)
+    };
+    if r >= b - r {
+        q + T::one()
+    } else {
+        q
+    }
+}
+
+/// Largest multiple of `m` less than or equal to `x`.
+pub fn floor_to_multiple<T: PrimInt>(x: T, m: T) -> T {
+    div_floor(x, m) * m
+}
+
+/// Smallest multiple of `m` greater than or equal to `x`.
+pub fn ceil_to_multiple<T: PrimInt>(x: T, m: T) -> T {
+    div_ceil(x, m) * m
+}
@@ -0,0 +1,57 @@
+use super::div::{ceil_to_multiple, div_ceil, div_floor, floor_to_multiple, rem_floor, round_div};
+
+/// Reference by scanning candidates, no division involved.
+fn reference_floor(a: i64, b: i64) -> i64 {
+    (-100..=100)
+        .rev()
+        .find(|&q| q * b <= a && b > 0 || q * b >= a && b < 0)
+        .unwrap()
+}
+
+#[test]
+fn div_grid() {
+    for a in -50i64..=50 {
+        for b in (-12i64..=12).filter(|&b| b != 0) {
+            let floor = reference_floor(a, b);
+            let ceil = if floor * b == a { floor } else { floor + 1 };
+
+            assert_eq!(div_floor(a, b), floor, "{} / {}", a, b);
+            assert_eq!(div_ceil(a, b), ceil, "{} / {}", a, b);
+            assert_eq!(div_floor(a as i128, b as i128), floor as i128);
+
+            let rem = rem_floor(a, b);
+            assert!((0..b.abs()).contains(&rem));
+            assert_eq!((a - rem) % b, 0);
+
+            // nearest by comparing doubled distances, ties go up
+            let round = if 2 * (a - floor * b).abs() >= b.abs() {
+                floor + 1
+            } else {
+                floor
+            };
+            assert_eq!(round_div(a, b), round, "{} / {}", a, b);
+
+            if b > 0 {
+                assert_eq!(floor_to_multiple(a, b), floor * b);
+                assert_eq!(ceil_to_multiple(a, b), ceil * b);
+            }
+        }
+    }
+}
+
+#[test]
+fn div_documented_cases() {
+    assert_eq!(div_floor(-7, 2), -4);
+    assert_eq!(div_ceil(-7, 2), -3);
+    assert_eq!(rem_floor(-7, 2), 1);
+    assert_eq!(round_div(-3, 2), -1);
+    assert_eq!(round_div(3, 2), 2);
+    assert_eq!(div_floor(7u32, 2), 3);
+    assert_eq!(div_floor(i64::MIN, 1), i64::MIN);
+}
+
+#[test]
+#[should_panic]
+fn div_by_zero() {
+    div_floor(1, 0);
+}
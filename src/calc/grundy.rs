@@ -0,0 +1,69 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Minimum excluded value, the smallest non-negative integer not in `values`.
+pub fn mex(values: impl IntoIterator<Item = usize>) -> usize {
+    let mut seen = Vec::new();
+    for v in values {
+        if v >= seen.len() {
+            seen.resize(v + 1, false);
+        }
+        seen[v] = true;
+    }
+    seen.iter().position(|&b| !b).unwrap_or(seen.len())
+}
+
+/// Grundy value of a sum of independent games.
+pub fn xor_sum(values: impl IntoIterator<Item = usize>) -> usize {
+    values.into_iter().fold(0, |acc, v| acc ^ v)
+}
+
+/// The game graph has a cycle, so Grundy values are undefined.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CyclicGame;
+
+/// Compute the Grundy value of each of `start_states`.
+///
+/// `moves(s)` lists the states reachable from `s` in one move.
+/// Values are memoized across all starts and the search is iterative, so deep games don't overflow the stack.
+pub fn grundy<S, I, F>(start_states: I, moves: F) -> Result<Vec<usize>, CyclicGame>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: Fn(&S) -> Vec<S>,
+{
+    let mut memo: HashMap<S, usize> = HashMap::new();
+    let mut on_stack = HashSet::new();
+    let mut values = Vec::new();
+
+    for start in start_states {
+        if !memo.contains_key(&start) {
+            // (state, next states, number of next states already processed)
+            let mut stack = vec![(start.clone(), moves(&start), 0)];
+            on_stack.insert(start.clone());
+
+            while let Some((state, next, i)) = stack.last_mut() {
+                if let Some(child) = next.get(*i) {
+                    *i += 1;
+                    if memo.contains_key(child) {
+                        continue;
+                    }
+                    if !on_stack.insert(child.clone()) {
+                        return Err(CyclicGame);
+                    }
+                    let child = child.clone();
+                    let child_next = moves(&child);
+                    stack.push((child, child_next, 0));
+                } else {
+                    let g = mex(next.iter().map(|s| memo[s]));
+                    on_stack.remove(state);
+                    memo.insert(state.clone(), g);
+                    stack.pop();
+                }
+            }
+        }
+        values.push(memo[&start]);
+    }
+
+    Ok(values)
+}
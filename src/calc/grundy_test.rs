@@ -0,0 +1,64 @@
+use super::grundy::{grundy, mex, xor_sum, CyclicGame};
+
+fn subtraction(n: &usize) -> Vec<usize> {
+    [1, 3, 4]
+        .iter()
+        .filter(|&&k| k <= *n)
+        .map(|k| n - k)
+        .collect()
+}
+
+#[test]
+fn mex_test() {
+    assert_eq!(mex(vec![]), 0);
+    assert_eq!(mex(vec![0, 1, 3]), 2);
+    assert_eq!(mex(vec![2, 1, 0, 0]), 3);
+}
+
+#[test]
+fn grundy_nim() {
+    let g = grundy(0..50usize, |&n| (0..n).collect()).unwrap();
+    assert_eq!(g, (0..50).collect::<Vec<_>>());
+    assert_eq!(xor_sum(vec![1, 2, 3]), 0);
+}
+
+#[test]
+fn grundy_subtraction_period() {
+    let g = grundy(0..100usize, subtraction).unwrap();
+    let period = [0, 1, 0, 1, 2, 3, 2];
+    for (n, v) in g.into_iter().enumerate() {
+        assert_eq!(v, period[n % 7], "n = {}", n);
+    }
+
+    // deep chain without stack overflow
+    assert_eq!(
+        grundy(vec![200_000usize], subtraction).unwrap(),
+        vec![period[200_000 % 7]]
+    );
+}
+
+#[test]
+fn grundy_composite_against_search() {
+    // two subtraction piles, the mover who cannot move loses
+    let n = 15;
+    let mut win = vec![vec![false; n]; n];
+    for a in 0..n {
+        for b in 0..n {
+            win[a][b] = subtraction(&a).iter().any(|&a2| !win[a2][b])
+                || subtraction(&b).iter().any(|&b2| !win[a][b2]);
+        }
+    }
+
+    let g = grundy(0..n, subtraction).unwrap();
+    for a in 0..n {
+        for b in 0..n {
+            assert_eq!(xor_sum(vec![g[a], g[b]]) != 0, win[a][b]);
+        }
+    }
+}
+
+#[test]
+fn grundy_cyclic() {
+    let r = grundy(vec![0u8], |&s| vec![(s + 1) % 3]);
+    assert_eq!(r, Err(CyclicGame));
+}
@@ -1,8 +1,28 @@
+pub mod bigint;
+pub mod dirichlet;
+pub mod div;
 pub mod gcd;
+pub mod grundy;
 pub mod prime;
+pub mod quotient;
+
+#[cfg(test)]
+mod bigint_test;
+
+#[cfg(test)]
+mod dirichlet_test;
+
+#[cfg(test)]
+mod div_test;
 
 #[cfg(test)]
 mod gcd_test;
 
+#[cfg(test)]
+mod grundy_test;
+
 #[cfg(test)]
 mod prime_test;
+
+#[cfg(test)]
+mod quotient_test;
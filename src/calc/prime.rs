@@ -32,3 +32,154 @@ impl PrimeFactorizer {
         }
     }
 }
+
+/// Returns the number of primes less than or equal to `n`.
+///
+/// Lucy_Hedgehog's method, `O(n^(3/4))` time and `O(sqrt(n))` memory.
+pub fn prime_count(n: u64) -> u64 {
+    lucy_hedgehog(n, |v| v as u128 - 1, |_| 1) as u64
+}
+
+/// Returns the sum of primes less than or equal to `n`.
+pub fn sum_of_primes(n: u64) -> u128 {
+    lucy_hedgehog(n, |v| v as u128 * (v as u128 + 1) / 2 - 1, |p| p as u128)
+}
+
+/// Sieve `f(p)` summed over primes `p <= v` for every quotient `v = n / i`.
+///
+/// `prefix(v)` is the sum of `f(k)` over `2..=v`, `f` must be completely multiplicative.
+fn lucy_hedgehog(n: u64, prefix: impl Fn(u64) -> u128, f: impl Fn(u64) -> u128) -> u128 {
+    if n < 2 {
+        return 0;
+    }
+    let r = isqrt(n);
+
+    // lo[v] holds S(v) for v <= r, hi[i] holds S(n / i) for i <= r.
+    let mut lo = (0..=r)
+        .map(|v| if v == 0 { 0 } else { prefix(v) })
+        .collect::<Vec<_>>();
+    let mut hi = (0..=r)
+        .map(|i| if i == 0 { 0 } else { prefix(n / i) })
+        .collect::<Vec<_>>();
+
+    for p in 2..=r {
+        // p is composite
+        if lo[p as usize] == lo[p as usize - 1] {
+            continue;
+        }
+        let sp = lo[p as usize - 1];
+        let fp = f(p);
+        let p2 = p * p;
+
+        for i in 1..=r.min(n / p2) {
+            let s = if i * p <= r {
+                hi[(i * p) as usize]
+            } else {
+                lo[(n / (i * p)) as usize]
+            };
+            hi[i as usize] -= fp * (s - sp);
+        }
+        for v in (p2..=r).rev() {
+            lo[v as usize] -= fp * (lo[(v / p) as usize] - sp);
+        }
+    }
+
+    hi[1]
+}
+
+/// Floor of the square root of `n`, exact up to `u64::MAX`.
+pub(crate) fn isqrt(n: u64) -> u64 {
+    // the float estimate may be off by one either way, and squaring it may overflow
+    let mut r = (n as f64).sqrt() as u64;
+    while r.checked_mul(r).map_or(true, |sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).map_or(false, |sq| sq <= n) {
+        r += 1;
+    }
+    r
+}
+
+const PRIMES_MAX_SEGMENT: u64 = 1 << 18;
+
+/// Unbounded increasing sequence of primes.
+///
+/// Sieves consecutive segments whose length doubles up to a cache friendly cap.
+/// Sieving primes are taken from a lazily created inner `Primes`,
+/// so memory stays `O(sqrt(p))` for the current prime `p`.
+pub struct Primes {
+    base: Option<Box<Primes>>,
+    base_primes: Vec<u64>,
+    segment: Vec<u64>,
+    idx: usize,
+    lo: u64,
+    len: u64,
+}
+
+impl Primes {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            base: None,
+            base_primes: Vec::new(),
+            segment: Vec::new(),
+            idx: 0,
+            lo: 2,
+            len: 64,
+        }
+    }
+
+    /// Sieve `lo..lo + len` and advance to the next segment.
+    fn sieve_next(&mut self) {
+        let (lo, hi) = (self.lo, self.lo + self.len);
+
+        if lo > 2 {
+            let base = self.base.get_or_insert_with(|| Box::new(Primes::new()));
+            while self.base_primes.last().map_or(true, |&p| p * p < hi) {
+                self.base_primes.push(base.next().unwrap());
+            }
+        }
+
+        let mut composite = vec![false; (hi - lo) as usize];
+        for &p in &self.base_primes {
+            let start = (p * p).max((lo + p - 1) / p * p);
+            let mut m = start;
+            while m < hi {
+                composite[(m - lo) as usize] = true;
+                m += p;
+            }
+        }
+        if lo == 2 {
+            // the first segment is small enough to sieve by trial division
+            for n in lo..hi {
+                let is_prime = (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0);
+                composite[(n - lo) as usize] = !is_prime;
+            }
+        }
+
+        self.segment.clear();
+        self.segment
+            .extend((lo..hi).filter(|&n| !composite[(n - lo) as usize]));
+        self.idx = 0;
+        self.lo = hi;
+        self.len = (self.len * 2).min(PRIMES_MAX_SEGMENT);
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.idx == self.segment.len() {
+            self.sieve_next();
+        }
+        self.idx += 1;
+        Some(self.segment[self.idx - 1])
+    }
+}
+
+/// Returns the `k`-th prime, 1-indexed (`nth_prime(1) == 2`).
+pub fn nth_prime(k: usize) -> u64 {
+    assert!(k > 0, "primes are 1-indexed");
+    Primes::new().nth(k - 1).unwrap()
+}
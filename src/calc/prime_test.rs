@@ -1,4 +1,4 @@
-use super::prime::PrimeFactorizer;
+use super::prime::{isqrt, nth_prime, prime_count, sum_of_primes, PrimeFactorizer, Primes};
 
 #[test]
 fn prime_factorizer() {
@@ -9,3 +9,58 @@ fn prime_factorizer() {
 
     assert_eq!(factors, vec![2, 5]);
 }
+
+#[test]
+fn prime_count_test() {
+    assert_eq!(prime_count(0), 0);
+    assert_eq!(prime_count(1), 0);
+    assert_eq!(prime_count(1_000_000), 78498);
+    assert_eq!(prime_count(1_000_000_000), 50847534);
+
+    let p = PrimeFactorizer::prepare(10_000);
+    let mut count = 0;
+    let mut sum = 0;
+    for n in 2..=10_000 {
+        let mut factors = Vec::new();
+        p.factorize(n, &mut factors);
+        if factors == [n] {
+            count += 1;
+            sum += n as u128;
+        }
+        assert_eq!(prime_count(n as u64), count);
+        assert_eq!(sum_of_primes(n as u64), sum);
+    }
+}
+
+#[test]
+fn isqrt_test() {
+    for n in 0..10_000u64 {
+        let r = isqrt(n);
+        assert!(r * r <= n && n < (r + 1) * (r + 1));
+    }
+    assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    assert_eq!(isqrt(u64::MAX - 1), u32::MAX as u64);
+    let r = u32::MAX as u64;
+    assert_eq!(isqrt(r * r), r);
+    assert_eq!(isqrt(r * r - 1), r - 1);
+}
+
+#[test]
+fn primes_iter() {
+    assert_eq!(
+        Primes::new().take(20).collect::<Vec<_>>(),
+        vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71]
+    );
+    assert_eq!(nth_prime(1), 2);
+    assert_eq!(nth_prime(100_000), 1_299_709);
+
+    let p = PrimeFactorizer::prepare(100_000);
+    let sieved = (2..=100_000)
+        .filter(|&n| {
+            let mut factors = Vec::new();
+            p.factorize(n, &mut factors);
+            factors == [n]
+        })
+        .map(|n| n as u64);
+    assert!(Primes::new().take_while(|&p| p <= 100_000).eq(sieved));
+}
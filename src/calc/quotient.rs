@@ -0,0 +1,25 @@
+/// Enumerate the blocks of `i` in `1..=n` sharing the same quotient `n / i`.
+///
+/// Yields `(l, r, q)` such that `n / i == q` for every `i` in `l..=r`.
+/// Blocks are in increasing order of `l` and cover `1..=n` exactly once,
+/// so there are only `O(sqrt(n))` of them.
+pub fn quotient_blocks(n: u64) -> impl Iterator<Item = (u64, u64, u64)> {
+    let mut l = 1;
+    std::iter::from_fn(move || {
+        if l > n {
+            return None;
+        }
+        let q = n / l;
+        let r = n / q;
+        let block = (l, r, q);
+        l = r + 1;
+        Some(block)
+    })
+}
+
+/// Returns `sum(n / i)` for `i` in `1..=n`.
+pub fn sum_floor_div(n: u64) -> u128 {
+    quotient_blocks(n)
+        .map(|(l, r, q)| (r - l + 1) as u128 * q as u128)
+        .sum()
+}
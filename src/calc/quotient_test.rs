@@ -0,0 +1,30 @@
+use super::quotient::{quotient_blocks, sum_floor_div};
+
+#[test]
+fn quotient_blocks_cover() {
+    let ns = (1..=1000).chain([9_999_991, 10u64.pow(12)]);
+    for n in ns {
+        let mut next = 1;
+        for (l, r, q) in quotient_blocks(n) {
+            assert_eq!(l, next);
+            assert!(l <= r);
+            assert_eq!(n / l, q);
+            assert_eq!(n / r, q);
+            if r < n {
+                assert_ne!(n / (r + 1), q);
+            }
+            next = r + 1;
+        }
+        assert_eq!(next - 1, n);
+    }
+}
+
+#[test]
+fn sum_floor_div_test() {
+    let brute = |n: u64| (1..=n).map(|i| (n / i) as u128).sum::<u128>();
+
+    assert_eq!(sum_floor_div(1), 1);
+    for n in (1..=2000).chain([123_456, 1_000_000]) {
+        assert_eq!(sum_floor_div(n), brute(n));
+    }
+}
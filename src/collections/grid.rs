@@ -0,0 +1,63 @@
+//! Transforms for `h x w` grids stored as `Vec<Vec<T>>`, rows must have equal length.
+
+fn width<T>(grid: &[Vec<T>]) -> usize {
+    grid.first().map_or(0, Vec::len)
+}
+
+/// `w x h` grid with `result[j][i] == grid[i][j]`.
+pub fn transpose<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    (0..width(grid))
+        .map(|j| grid.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// Rotate 90 degrees clockwise, `h x w` becomes `w x h`.
+pub fn rotate_cw<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    (0..width(grid))
+        .map(|j| grid.iter().rev().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// Rotate 90 degrees counterclockwise, `h x w` becomes `w x h`.
+pub fn rotate_ccw<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    (0..width(grid))
+        .rev()
+        .map(|j| grid.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// Mirror left and right.
+pub fn flip_horizontal<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    grid.iter()
+        .map(|row| row.iter().rev().cloned().collect())
+        .collect()
+}
+
+/// Mirror top and bottom.
+pub fn flip_vertical<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    grid.iter().rev().cloned().collect()
+}
+
+/// Smallest sub-grid containing every cell for which `is_empty` is `false`.
+///
+/// Returns an empty grid if every cell is empty.
+pub fn trim_to_bounding_box<T, F>(grid: &[Vec<T>], is_empty: F) -> Vec<Vec<T>>
+where
+    T: Clone,
+    F: Fn(&T) -> bool,
+{
+    let filled = |i: usize, j: usize| !is_empty(&grid[i][j]);
+    let (h, w) = (grid.len(), width(grid));
+    let rows = (0..h).filter(|&i| (0..w).any(|j| filled(i, j)));
+    let cols = (0..w).filter(|&j| (0..h).any(|i| filled(i, j)));
+
+    let (Some(top), Some(left)) = (rows.clone().next(), cols.clone().next()) else {
+        return vec![];
+    };
+    let (bottom, right) = (rows.last().unwrap(), cols.last().unwrap());
+
+    grid[top..=bottom]
+        .iter()
+        .map(|row| row[left..=right].to_vec())
+        .collect()
+}
@@ -0,0 +1,37 @@
+use crate::collections::grid::*;
+
+fn chars(rows: &[&str]) -> Vec<Vec<char>> {
+    rows.iter().map(|r| r.chars().collect()).collect()
+}
+
+#[test]
+fn grid_rotate() {
+    let g = chars(&["abc", "def"]);
+
+    assert_eq!(rotate_cw(&g), chars(&["da", "eb", "fc"]));
+    assert_eq!(rotate_ccw(&g), chars(&["cf", "be", "ad"]));
+    assert_eq!(rotate_ccw(&rotate_cw(&g)), g);
+
+    let r = (0..4).fold(g.clone(), |r, _| rotate_cw(&r));
+    assert_eq!(r, g);
+}
+
+#[test]
+fn grid_transpose_flip() {
+    let g = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+    assert_eq!(transpose(&g), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    assert_eq!(flip_horizontal(&g), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+    assert_eq!(flip_vertical(&g), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+    assert_eq!(transpose::<i32>(&[]), Vec::<Vec<i32>>::new());
+}
+
+#[test]
+fn grid_trim() {
+    let g = chars(&["......", "..#...", "...##.", "......"]);
+    assert_eq!(
+        trim_to_bounding_box(&g, |&c| c == '.'),
+        chars(&["#..", ".##"])
+    );
+    assert!(trim_to_bounding_box(&chars(&["..", ".."]), |&c| c == '.').is_empty());
+}
@@ -1,5 +1,9 @@
+pub mod grid;
 pub mod segment_tree;
 pub mod unionfind;
 
+#[cfg(test)]
+mod grid_test;
+
 #[cfg(test)]
 mod segment_tree_test;
@@ -30,7 +30,7 @@ where
         let mut buf = vec![init; pw2];
         buf.append(&mut other);
 
-        for i in (1..pw2).into_iter().rev() {
+        for i in (1..pw2).rev() {
             buf[i] = f(buf[i * 2], buf[i * 2 + 1]);
         }
 
@@ -1,6 +1,14 @@
 pub mod cio {
-    use std::fmt::{self, Debug};
-    use std::io::{BufRead, Cursor, Stdin, StdinLock};
+    use std::cell::RefCell;
+    use std::convert::TryInto;
+    use std::fmt::{self, Debug, Display};
+    use std::fs::File;
+    use std::io::{
+        self, BufRead, BufReader, BufWriter, Cursor, Read, Stdin, StdinLock, Stdout, StdoutLock,
+        Write,
+    };
+    use std::ops::Deref;
+    use std::path::Path;
     use std::str::FromStr;
 
     const INITIAL_BUF_SIZE: usize = 1024;
@@ -9,20 +17,72 @@ pub mod cio {
 
     #[derive(Debug)]
     pub enum Error {
-        Io { source: std::io::Error },
-        Utf8 { source: std::str::Utf8Error },
-        Parse { message: String },
+        Io {
+            source: std::io::Error,
+        },
+        Utf8 {
+            source: std::str::Utf8Error,
+        },
+        Parse {
+            message: String,
+            token: String,
+            type_name: &'static str,
+            /// 1-indexed line of the token.
+            line: usize,
+            /// 1-indexed position of the token in its line.
+            index: usize,
+        },
+        /// A buffer of `found` elements was given for `expected` values.
+        Length {
+            expected: usize,
+            found: usize,
+        },
         Eof,
     }
 
     impl fmt::Display for Error {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            write!(f, "{:?}", self)
+            match self {
+                Error::Parse {
+                    message,
+                    token,
+                    type_name,
+                    line,
+                    index,
+                } => write!(
+                    f,
+                    "failed to parse {:?} as {} at line {}, token {}: {}",
+                    token, type_name, line, index, message
+                ),
+                _ => write!(f, "{:?}", self),
+            }
         }
     }
 
     impl std::error::Error for Error {}
 
+    impl Error {
+        /// Prefix a parse error with the field it was scanned for.
+        pub fn in_field(self, field: &str) -> Self {
+            match self {
+                Error::Parse {
+                    message,
+                    token,
+                    type_name,
+                    line,
+                    index,
+                } => Error::Parse {
+                    message: format!("field `{}`: {}", field, message),
+                    token,
+                    type_name,
+                    line,
+                    index,
+                },
+                err => err,
+            }
+        }
+    }
+
     impl From<std::io::Error> for Error {
         fn from(source: std::io::Error) -> Self {
             Error::Io { source }
@@ -35,18 +95,13 @@ pub mod cio {
         }
     }
 
-    impl Error {
-        fn parse_error<E: Debug>(err: E) -> Self {
-            Error::Parse {
-                message: format!("{:?}", err),
-            }
-        }
-    }
-
     pub struct Scanner<R> {
         reader: R,
         buf: Vec<u8>,
         pos: usize,
+        // position of the last token for errors
+        line: usize,
+        line_tokens: usize,
     }
 
     impl<'a> From<&'a Stdin> for Scanner<StdinLock<'a>> {
@@ -55,12 +110,28 @@ pub mod cio {
         }
     }
 
+    impl From<File> for Scanner<BufReader<File>> {
+        fn from(file: File) -> Self {
+            Scanner::new(BufReader::new(file))
+        }
+    }
+
+    impl Scanner<BufReader<File>> {
+        /// Scan a file, e.g. a sample input saved locally.
+        pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+            Ok(Scanner::from(File::open(path)?))
+        }
+    }
+
     impl<'a> From<&'a str> for Scanner<Cursor<&'a str>> {
         fn from(s: &'a str) -> Self {
             Scanner::new(Cursor::new(s))
         }
     }
 
+    #[cfg(feature = "derive")]
+    pub use atcoder_lib_derive::FromScanner;
+
     pub trait FromScanner<R>
     where
         R: BufRead,
@@ -109,29 +180,289 @@ pub mod cio {
             }
         }
     }
-    impl_scanner!(usize);
-    impl_scanner!(isize);
-    impl_scanner!(u8);
-    impl_scanner!(u32);
-    impl_scanner!(u64);
-    impl_scanner!(i8);
-    impl_scanner!(i32);
-    impl_scanner!(i64);
+    /// Integers scanned straight from the bytes by [`Scanner::scan_int`].
+    trait Int: Copy + FromStr {
+        const SIGNED: bool;
+        const ZERO: Self;
+        /// `self * 10 + d`, or `- d` for negative numbers, `None` on overflow.
+        fn push_digit(self, d: u8, negative: bool) -> Option<Self>;
+    }
+
+    macro_rules! impl_int {
+        ($($t:ty: $signed:expr),+) => {
+            $(
+                impl Int for $t {
+                    const SIGNED: bool = $signed;
+                    const ZERO: Self = 0;
+
+                    fn push_digit(self, d: u8, negative: bool) -> Option<Self> {
+                        let v = self.checked_mul(10)?;
+                        if negative {
+                            v.checked_sub(d as $t)
+                        } else {
+                            v.checked_add(d as $t)
+                        }
+                    }
+                }
+
+                impl<R> FromScanner<R> for $t
+                where
+                    R: BufRead,
+                {
+                    fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+                    where
+                        Self: Sized,
+                    {
+                        s.scan_int()
+                    }
+                }
+            )+
+        };
+    }
+    impl_int!(
+        usize: false,
+        u8: false,
+        u16: false,
+        u32: false,
+        u64: false,
+        u128: false,
+        isize: true,
+        i8: true,
+        i16: true,
+        i32: true,
+        i64: true,
+        i128: true
+    );
+    impl_scanner!(f32);
+    impl_scanner!(f64);
     impl_scanner!(String);
     impl_scanner!(char);
     impl_scanner!(T1, T2);
     impl_scanner!(T1, T2, T3);
     impl_scanner!(T1, T2, T3, T4);
 
+    impl<R, T, const N: usize> FromScanner<R> for [T; N]
+    where
+        R: BufRead,
+        T: FromScanner<R>,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            let mut v = Vec::with_capacity(N);
+            for _ in 0..N {
+                v.push(T::try_from_scanner(s)?);
+            }
+            Ok(v.try_into().unwrap_or_else(|_| unreachable!()))
+        }
+    }
+
+    /// A token as chars, for indexing into strings and grid rows.
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    pub struct Chars(pub Vec<char>);
+
+    impl<R> FromScanner<R> for Chars
+    where
+        R: BufRead,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            let token: String = s.try_parse()?;
+            Ok(Chars(token.chars().collect()))
+        }
+    }
+
+    impl Deref for Chars {
+        type Target = [char];
+
+        fn deref(&self) -> &[char] {
+            &self.0
+        }
+    }
+
+    /// A token as raw bytes, skipping UTF-8 validation.
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    pub struct Bytes(pub Vec<u8>);
+
+    impl<R> FromScanner<R> for Bytes
+    where
+        R: BufRead,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            Ok(Bytes(s.next_token_bytes()?.to_vec()))
+        }
+    }
+
+    impl Deref for Bytes {
+        type Target = [u8];
+
+        fn deref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    /// A flag read from `Yes`/`No`, `YES`/`NO` or `1`/`0` and displayed as `Yes`/`No`.
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct YesNo(pub bool);
+
+    impl<R> FromScanner<R> for YesNo
+    where
+        R: BufRead,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            match s.next_token_bytes()? {
+                b"Yes" | b"YES" | b"1" => Ok(YesNo(true)),
+                b"No" | b"NO" | b"0" => Ok(YesNo(false)),
+                token => {
+                    let token = String::from_utf8_lossy(token).into_owned();
+                    Err(s.parse_error::<Self>(&token, "expected Yes or No"))
+                }
+            }
+        }
+    }
+
+    impl Display for YesNo {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(if self.0 { "Yes" } else { "No" })
+        }
+    }
+
+    /// Integers parsable in a given radix, for [`Hex`] and [`Bin`].
+    pub trait FromStrRadix: Sized {
+        fn from_str_radix(
+            s: &str,
+            radix: u32,
+        ) -> std::result::Result<Self, std::num::ParseIntError>;
+    }
+
+    macro_rules! impl_from_str_radix {
+        ($($t:ty),+) => {
+            $(impl FromStrRadix for $t {
+                fn from_str_radix(s: &str, radix: u32) -> std::result::Result<Self, std::num::ParseIntError> {
+                    <$t>::from_str_radix(s, radix)
+                }
+            })+
+        };
+    }
+    impl_from_str_radix!(u8, u16, u32, u64, u128, usize);
+
+    fn scan_radix<R, T>(s: &mut Scanner<R>, radix: u32, prefixes: [&str; 2]) -> Result<T>
+    where
+        R: BufRead,
+        T: FromStrRadix,
+    {
+        let token: String = s.try_parse()?;
+        let digits = prefixes
+            .iter()
+            .find_map(|p| token.strip_prefix(p))
+            .unwrap_or(&token);
+        T::from_str_radix(digits, radix)
+            .map_err(|err| s.parse_error::<T>(&token, format!("{:?}", err)))
+    }
+
+    /// An integer written in hexadecimal, optionally prefixed with `0x`.
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Hex<T>(pub T);
+
+    impl<R, T> FromScanner<R> for Hex<T>
+    where
+        R: BufRead,
+        T: FromStrRadix,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            scan_radix(s, 16, ["0x", "0X"]).map(Hex)
+        }
+    }
+
+    /// An integer written in binary, optionally prefixed with `0b`.
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Bin<T>(pub T);
+
+    impl<R, T> FromScanner<R> for Bin<T>
+    where
+        R: BufRead,
+        T: FromStrRadix,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            scan_radix(s, 2, ["0b", "0B"]).map(Bin)
+        }
+    }
+
+    /// A 1-indexed usize converted to 0-indexed.
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+    pub struct Usize1(pub usize);
+
+    impl<R> FromScanner<R> for Usize1
+    where
+        R: BufRead,
+    {
+        fn try_from_scanner(s: &mut Scanner<R>) -> Result<Self>
+        where
+            Self: Sized,
+        {
+            match usize::try_from_scanner(s)? {
+                0 => Err(s.parse_error::<Self>("0", "must not be 0")),
+                n => Ok(Usize1(n - 1)),
+            }
+        }
+    }
+
+    impl From<Usize1> for usize {
+        fn from(n: Usize1) -> Self {
+            n.0
+        }
+    }
+
+    fn is_separator(b: u8) -> bool {
+        b.is_ascii_whitespace()
+    }
+
+    impl Scanner<io::Empty> {
+        /// Read the whole input up front and scan it from one buffer.
+        ///
+        /// Faster than line by line for huge inputs, but unusable for interactive problems.
+        pub fn preload<R: Read>(mut reader: R) -> Self {
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf).unwrap();
+            if buf.last() != Some(&b'\n') {
+                buf.push(b'\n');
+            }
+            Self {
+                reader: io::empty(),
+                buf,
+                pos: 0,
+                line: 1,
+                line_tokens: 0,
+            }
+        }
+    }
+
     impl<R> Scanner<R>
     where
         R: BufRead,
     {
-        fn new(reader: R) -> Self {
+        pub fn new(reader: R) -> Self {
             Self {
                 reader,
                 buf: Vec::with_capacity(INITIAL_BUF_SIZE),
                 pos: 0,
+                line: 1,
+                line_tokens: 0,
             }
         }
 
@@ -153,96 +484,704 @@ pub mod cio {
             v
         }
 
-        pub fn parse<T>(&mut self) -> T
+        /// Scan `n` values onto the end of `out`.
+        pub fn scan_extend<T>(&mut self, n: usize, out: &mut Vec<T>)
         where
-            T: FromStr,
-            <T as FromStr>::Err: Debug,
+            T: FromScanner<R>,
         {
-            match self.try_parse() {
-                Ok(v) => v,
+            out.reserve(n);
+            for _ in 0..n {
+                out.push(T::from_scanner(self));
+            }
+        }
+
+        pub fn scan_into<T>(&mut self, n: usize, out: &mut [T])
+        where
+            T: FromScanner<R>,
+        {
+            match self.try_scan_into(n, out) {
+                Ok(()) => (),
                 Err(err) => panic!("{}", err),
             }
         }
 
-        pub fn try_parse<T>(&mut self) -> Result<T>
+        /// Overwrite `out` with `n` scanned values, `out` must hold exactly `n`.
+        pub fn try_scan_into<T>(&mut self, n: usize, out: &mut [T]) -> Result<()>
         where
-            T: FromStr,
-            <T as FromStr>::Err: Debug,
+            T: FromScanner<R>,
         {
-            if self.buf.is_empty() {
-                self.fill_buf()?;
+            if out.len() != n {
+                return Err(Error::Length {
+                    expected: n,
+                    found: out.len(),
+                });
             }
+            for x in out {
+                *x = T::try_from_scanner(self)?;
+            }
+            Ok(())
+        }
 
-            let mut from = None;
+        /// Scan a `T`, returning [`Error::Eof`] if the input ends first and
+        /// [`Error::Parse`] if a token doesn't parse, instead of panicking like [`Scanner::scan`].
+        ///
+        /// The scanner is not rewound on error: tokens already read for `T`,
+        /// including the one which failed to parse, stay consumed.
+        pub fn try_scan<T>(&mut self) -> Result<T>
+        where
+            T: FromScanner<R>,
+        {
+            T::try_from_scanner(self)
+        }
 
-            loop {
-                match (self.buf[self.pos], from.is_some()) {
-                    // ignore space
-                    (b' ', false) => self.pos += 1,
+        /// Scan `n` values, stopping at the first error.
+        ///
+        /// Values scanned before the error are discarded.
+        pub fn try_scan_n<T>(&mut self, n: usize) -> Result<Vec<T>>
+        where
+            T: FromScanner<R>,
+        {
+            let mut v = Vec::with_capacity(n);
+            for _ in 0..n {
+                v.push(T::try_from_scanner(self)?);
+            }
+            Ok(v)
+        }
 
-                    // read all, so handle next line
-                    (b'\n', false) => self.fill_buf()?,
+        /// Scan values of `T` until the input ends.
+        ///
+        /// Panics on any other error, including input ending in the middle of a `T`.
+        pub fn iter<T>(&mut self) -> impl Iterator<Item = T> + '_
+        where
+            T: FromScanner<R>,
+        {
+            std::iter::from_fn(move || {
+                if !self.has_next() {
+                    return None;
+                }
+                match T::try_from_scanner(self) {
+                    Ok(v) => Some(v),
+                    Err(err) => panic!("{}", err),
+                }
+            })
+        }
 
-                    // found target start index
-                    (_, false) => {
-                        from = Some(self.pos);
-                        self.pos += 1;
-                    }
+        pub fn scan_all<T>(&mut self) -> Vec<T>
+        where
+            T: FromScanner<R>,
+        {
+            self.iter().collect()
+        }
 
-                    // found target, try parse
-                    (b' ', true) | (b'\n', true) => break,
+        /// Scan a `T` and map it through `f`.
+        pub fn scan_with<T, U, F>(&mut self, mut f: F) -> U
+        where
+            T: FromScanner<R>,
+            F: FnMut(T) -> U,
+        {
+            f(self.scan())
+        }
 
-                    // keep checking
-                    (_, true) => self.pos += 1,
-                }
+        /// Scan `n` values of `T`, mapping each through `f` in order.
+        pub fn scan_n_with<T, U, F>(&mut self, n: usize, mut f: F) -> Vec<U>
+        where
+            T: FromScanner<R>,
+            F: FnMut(T) -> U,
+        {
+            let mut v = Vec::with_capacity(n);
+            for _ in 0..n {
+                v.push(f(self.scan()));
             }
-
-            let part = std::str::from_utf8(&self.buf[from.unwrap()..self.pos])?;
-            part.parse::<T>().map_err(Error::parse_error)
+            v
         }
 
-        /// read a line from underlying reader and store it in the buffer.
-        fn fill_buf(&mut self) -> Result<()> {
-            self.buf.clear();
-            self.pos = 0;
-            if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
-                Err(Error::Eof)
-            } else {
-                // ensure buf end in a newline
-                match self.buf.last() {
-                    Some(b'\n') => (),
-                    Some(_) | None => self.buf.push(b'\n'),
-                }
-                Ok(())
+        pub fn scan_cols2<A, B>(&mut self, n: usize) -> (Vec<A>, Vec<B>)
+        where
+            A: FromScanner<R>,
+            B: FromScanner<R>,
+        {
+            match self.try_scan_cols2(n) {
+                Ok(cols) => cols,
+                Err(err) => panic!("{}", err),
             }
         }
-    }
 
-    #[allow(unused_macros)]
-    macro_rules! setup {
-        ( $scanner:ident ) => {
-            let _stdin = std::io::stdin();
-            let mut $scanner = cio::Scanner::from(&_stdin);
-        };
-    }
-    #[allow(unused_imports)]
-    pub(crate) use setup;
-}
+        /// Read `n` rows of `a b` into a column of `a`s and a column of `b`s.
+        pub fn try_scan_cols2<A, B>(&mut self, n: usize) -> Result<(Vec<A>, Vec<B>)>
+        where
+            A: FromScanner<R>,
+            B: FromScanner<R>,
+        {
+            let (mut a, mut b) = (Vec::with_capacity(n), Vec::with_capacity(n));
+            for _ in 0..n {
+                a.push(A::try_from_scanner(self)?);
+                b.push(B::try_from_scanner(self)?);
+            }
+            Ok((a, b))
+        }
 
-#[cfg(test)]
-mod test {
-    use super::cio::*;
+        pub fn scan_cols3<A, B, C>(&mut self, n: usize) -> (Vec<A>, Vec<B>, Vec<C>)
+        where
+            A: FromScanner<R>,
+            B: FromScanner<R>,
+            C: FromScanner<R>,
+        {
+            match self.try_scan_cols3(n) {
+                Ok(cols) => cols,
+                Err(err) => panic!("{}", err),
+            }
+        }
 
-    #[test]
-    fn new() {
-        let stdin = std::io::stdin();
-        let _ = Scanner::from(&stdin);
+        /// Read `n` rows of `a b c` into three columns.
+        pub fn try_scan_cols3<A, B, C>(&mut self, n: usize) -> Result<(Vec<A>, Vec<B>, Vec<C>)>
+        where
+            A: FromScanner<R>,
+            B: FromScanner<R>,
+            C: FromScanner<R>,
+        {
+            let mut a = Vec::with_capacity(n);
+            let mut b = Vec::with_capacity(n);
+            let mut c = Vec::with_capacity(n);
+            for _ in 0..n {
+                a.push(A::try_from_scanner(self)?);
+                b.push(B::try_from_scanner(self)?);
+                c.push(C::try_from_scanner(self)?);
+            }
+            Ok((a, b, c))
+        }
 
-        let input = "1 2 3";
-        let _ = Scanner::from(input);
-    }
+        pub fn scan_matrix<T>(&mut self, h: usize, w: usize) -> Vec<Vec<T>>
+        where
+            T: FromScanner<R>,
+        {
+            match self.try_scan_matrix(h, w) {
+                Ok(matrix) => matrix,
+                Err(err) => panic!("{}", err),
+            }
+        }
 
-    #[test]
+        /// Read `h * w` values row by row, regardless of how they are split into lines.
+        pub fn try_scan_matrix<T>(&mut self, h: usize, w: usize) -> Result<Vec<Vec<T>>>
+        where
+            T: FromScanner<R>,
+        {
+            let mut matrix = Vec::with_capacity(h);
+            for _ in 0..h {
+                let mut row = Vec::with_capacity(w);
+                for _ in 0..w {
+                    row.push(T::try_from_scanner(self)?);
+                }
+                matrix.push(row);
+            }
+            Ok(matrix)
+        }
+
+        pub fn scan_grid(&mut self, h: usize, w: usize) -> Vec<Vec<char>> {
+            match self.try_scan_grid(h, w) {
+                Ok(grid) => grid,
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        /// Read `h` rows of exactly `w` chars each.
+        pub fn try_scan_grid(&mut self, h: usize, w: usize) -> Result<Vec<Vec<char>>> {
+            let mut grid = Vec::with_capacity(h);
+            for i in 0..h {
+                let token: String = self.try_parse()?;
+                let row = token.chars().collect::<Vec<_>>();
+                if row.len() != w {
+                    let message = format!("grid row {} has width {}, expected {}", i, row.len(), w);
+                    return Err(self.parse_error::<Vec<char>>(&token, message));
+                }
+                grid.push(row);
+            }
+            Ok(grid)
+        }
+
+        pub fn scan_line(&mut self) -> String {
+            match self.try_scan_line() {
+                Ok(line) => line,
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        /// Read the rest of the current line verbatim, without the line ending.
+        ///
+        /// The separator right after a previously scanned token is skipped,
+        /// and if nothing is left on the current line the next line is read instead.
+        pub fn try_scan_line(&mut self) -> Result<String> {
+            if self.pos == self.buf.len() {
+                self.fill_buf()?;
+            } else {
+                if matches!(self.buf[self.pos], b' ' | b'\t') {
+                    self.pos += 1;
+                }
+                let end = self.line_end();
+                if matches!(&self.buf[self.pos..end], b"" | b"\r") {
+                    self.pos = end + 1;
+                    self.next_line();
+                    if self.pos == self.buf.len() {
+                        self.fill_buf()?;
+                    }
+                }
+            }
+
+            let end = self.line_end();
+            let mut line = &self.buf[self.pos..end];
+            if let Some(stripped) = line.strip_suffix(b"\r") {
+                line = stripped;
+            }
+            let line = std::str::from_utf8(line)?.to_owned();
+            self.pos = end;
+            Ok(line)
+        }
+
+        pub fn parse<T>(&mut self) -> T
+        where
+            T: FromStr,
+            <T as FromStr>::Err: Debug,
+        {
+            match self.try_parse() {
+                Ok(v) => v,
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        pub fn try_parse<T>(&mut self) -> Result<T>
+        where
+            T: FromStr,
+            <T as FromStr>::Err: Debug,
+        {
+            let from = self.next_token()?;
+            let part = std::str::from_utf8(&self.buf[from..self.pos])?;
+            part.parse::<T>()
+                .map_err(|err| self.parse_error::<T>(part, format!("{:?}", err)))
+        }
+
+        /// Move past the next token and return where it starts.
+        fn next_token(&mut self) -> Result<usize> {
+            self.skip_separators()?;
+            let from = self.pos;
+            self.line_tokens += 1;
+            while self.pos < self.buf.len() && !is_separator(self.buf[self.pos]) {
+                self.pos += 1;
+            }
+            Ok(from)
+        }
+
+        /// The next token as bytes in the internal buffer.
+        pub fn next_token_bytes(&mut self) -> Result<&[u8]> {
+            let from = self.next_token()?;
+            Ok(&self.buf[from..self.pos])
+        }
+
+        pub fn skip(&mut self, n: usize) {
+            match self.try_skip(n) {
+                Ok(()) => (),
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        /// Discard the next `n` tokens.
+        pub fn try_skip(&mut self, n: usize) -> Result<()> {
+            for _ in 0..n {
+                self.next_token()?;
+            }
+            Ok(())
+        }
+
+        pub fn skip_line(&mut self) {
+            match self.try_skip_line() {
+                Ok(()) => (),
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        /// Discard everything up to and including the next newline.
+        pub fn try_skip_line(&mut self) -> Result<()> {
+            if self.pos == self.buf.len() {
+                self.fill_buf()?;
+            }
+            self.pos = self.line_end() + 1;
+            self.next_line();
+            Ok(())
+        }
+
+        /// Whether another token remains, without consuming it.
+        pub fn has_next(&mut self) -> bool {
+            self.skip_separators().is_ok()
+        }
+
+        /// Parse the next token without consuming it.
+        pub fn peek<T>(&mut self) -> Result<T>
+        where
+            T: FromStr,
+            <T as FromStr>::Err: Debug,
+        {
+            // after skipping, the token is in the buffer and parsing it won't refill
+            self.skip_separators()?;
+            let (pos, line_tokens) = (self.pos, self.line_tokens);
+            let v = self.try_parse();
+            self.pos = pos;
+            self.line_tokens = line_tokens;
+            v
+        }
+
+        /// The next token without consuming it.
+        pub fn peek_token(&mut self) -> Result<&str> {
+            self.skip_separators()?;
+            let len = self.buf[self.pos..]
+                .iter()
+                .position(|&b| is_separator(b))
+                .unwrap();
+            Ok(std::str::from_utf8(&self.buf[self.pos..self.pos + len])?)
+        }
+
+        /// Skip separators, reading lines until a token starts.
+        fn skip_separators(&mut self) -> Result<()> {
+            loop {
+                while self.pos < self.buf.len() && is_separator(self.buf[self.pos]) {
+                    if self.buf[self.pos] == b'\n' {
+                        self.next_line();
+                    }
+                    self.pos += 1;
+                }
+                if self.pos < self.buf.len() {
+                    return Ok(());
+                }
+                self.fill_buf()?;
+            }
+        }
+
+        /// Parse an integer token without UTF-8 validation nor `FromStr`.
+        fn scan_int<T>(&mut self) -> Result<T>
+        where
+            T: Int,
+            <T as FromStr>::Err: Debug,
+        {
+            let from = self.next_token()?;
+            let token = &self.buf[from..self.pos];
+            let (negative, digits) = match token {
+                [b'-', rest @ ..] if T::SIGNED => (true, rest),
+                [b'+', rest @ ..] => (false, rest),
+                _ => (false, token),
+            };
+            let mut v = Some(T::ZERO);
+            for &b in digits {
+                v = v
+                    .filter(|_| b.is_ascii_digit())
+                    .and_then(|v| v.push_digit(b - b'0', negative));
+            }
+            match v {
+                Some(v) if !digits.is_empty() => Ok(v),
+                // let FromStr describe what is wrong
+                _ => {
+                    let part = String::from_utf8_lossy(token).into_owned();
+                    let message = match part.parse::<T>() {
+                        Err(err) => format!("{:?}", err),
+                        Ok(_) => "invalid integer".to_owned(),
+                    };
+                    Err(self.parse_error::<T>(&part, message))
+                }
+            }
+        }
+
+        /// A parse error of `token` as `T`, located at the last scanned token.
+        pub fn parse_error<T>(&self, token: &str, message: impl Display) -> Error {
+            Error::Parse {
+                message: message.to_string(),
+                token: token.to_owned(),
+                type_name: std::any::type_name::<T>(),
+                line: self.line,
+                index: self.line_tokens,
+            }
+        }
+
+        fn next_line(&mut self) {
+            self.line += 1;
+            self.line_tokens = 0;
+        }
+
+        /// Index of the newline ending the current line, every buffer ends in one.
+        fn line_end(&self) -> usize {
+            self.pos
+                + self.buf[self.pos..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .unwrap()
+        }
+
+        /// read a line from underlying reader and store it in the buffer.
+        fn fill_buf(&mut self) -> Result<()> {
+            self.buf.clear();
+            self.pos = 0;
+            if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+                Err(Error::Eof)
+            } else {
+                // ensure buf end in a newline
+                match self.buf.last() {
+                    Some(b'\n') => (),
+                    Some(_) | None => self.buf.push(b'\n'),
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// `Fixed(x, n)` displays `x` with exactly `n` digits after the decimal point,
+    /// never in scientific notation.
+    ///
+    /// Values rounding to zero are shown without a sign.
+    #[derive(Clone, Copy, Debug)]
+    pub struct Fixed(pub f64, pub usize);
+
+    impl Display for Fixed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let s = format!("{:.*}", self.1, self.0);
+            match s.strip_prefix('-') {
+                Some(abs) if abs.bytes().all(|b| b == b'0' || b == b'.') => f.write_str(abs),
+                _ => f.write_str(&s),
+            }
+        }
+    }
+
+    /// Buffered output, flushed on drop.
+    ///
+    /// ```
+    /// use atcoder_lib::contest::cio::{Scanner, Writer};
+    ///
+    /// let mut scanner = Scanner::from("3\n1 2\n3 4\n5 6\n");
+    /// let mut writer = Writer::new(Vec::new());
+    /// let q = scanner.scan::<usize>();
+    /// for _ in 0..q {
+    ///     let (a, b) = scanner.scan::<(u64, u64)>();
+    ///     writer.writeln(a + b);
+    /// }
+    /// assert_eq!(writer.into_inner(), b"3\n7\n11\n");
+    /// ```
+    pub struct Writer<W: Write> {
+        inner: BufWriter<W>,
+    }
+
+    impl<'a> From<&'a Stdout> for Writer<StdoutLock<'a>> {
+        fn from(stdout: &'a Stdout) -> Self {
+            Writer::new(stdout.lock())
+        }
+    }
+
+    impl<W: Write> Writer<W> {
+        pub fn new(w: W) -> Self {
+            Self {
+                inner: BufWriter::new(w),
+            }
+        }
+
+        pub fn write<T: Display>(&mut self, v: T) {
+            write!(self.inner, "{}", v).unwrap();
+        }
+
+        pub fn writeln<T: Display>(&mut self, v: T) {
+            writeln!(self.inner, "{}", v).unwrap();
+        }
+
+        /// Write `items` separated by `sep`, nothing for an empty slice.
+        pub fn write_join<T: Display>(&mut self, items: &[T], sep: &str) {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    self.write(sep);
+                }
+                self.write(item);
+            }
+        }
+
+        /// [`Writer::write_join`] then a newline.
+        pub fn writeln_join<T: Display>(&mut self, items: &[T], sep: &str) {
+            self.write_join(items, sep);
+            self.writeln("");
+        }
+
+        /// Write each item on its own line.
+        pub fn writeln_each<T: Display>(&mut self, items: &[T]) {
+            for item in items {
+                self.writeln(item);
+            }
+        }
+
+        /// Write `a b` as a line.
+        pub fn write_pair<A: Display, B: Display>(&mut self, a: A, b: B) {
+            self.write(a);
+            self.write(' ');
+            self.writeln(b);
+        }
+
+        /// Write `x` with `digits` decimal places.
+        pub fn float(&mut self, x: f64, digits: usize) {
+            self.write(Fixed(x, digits));
+        }
+
+        /// Write `x` with 6 decimal places, enough for the usual `1e-6` tolerance.
+        pub fn float6(&mut self, x: f64) {
+            self.float(x, 6);
+        }
+
+        /// Write `Yes` or `No` as a line.
+        pub fn yes_no(&mut self, b: bool) {
+            self.writeln(YesNo(b));
+        }
+
+        pub fn flush(&mut self) {
+            self.inner.flush().unwrap();
+        }
+
+        /// Flush and return the underlying writer.
+        pub fn into_inner(self) -> W {
+            self.inner
+                .into_inner()
+                .unwrap_or_else(|err| panic!("{}", err.error()))
+        }
+    }
+
+    thread_local! {
+        static GLOBAL: RefCell<Option<Scanner<io::Empty>>> = RefCell::new(None);
+    }
+
+    fn with_global<T>(f: impl FnOnce(&mut Scanner<io::Empty>) -> T) -> T {
+        GLOBAL.with(|global| {
+            let mut global = global.borrow_mut();
+            let scanner = global.get_or_insert_with(|| Scanner::preload(io::stdin().lock()));
+            f(scanner)
+        })
+    }
+
+    /// Scan from a thread local scanner over the whole stdin, read on first use.
+    pub fn read<T: FromScanner<io::Empty>>() -> T {
+        with_global(|s| s.scan())
+    }
+
+    pub fn read_n<T: FromScanner<io::Empty>>(n: usize) -> Vec<T> {
+        with_global(|s| s.scan_n(n))
+    }
+
+    /// Make [`read`] scan `input` instead of stdin on this thread.
+    pub fn set_input(input: &str) {
+        GLOBAL.with(|global| *global.borrow_mut() = Some(Scanner::preload(input.as_bytes())));
+    }
+
+    /// Read the number of cases `T` and call `solve` for each.
+    pub fn run_cases<R, W, F>(scanner: &mut Scanner<R>, writer: &mut Writer<W>, mut solve: F)
+    where
+        R: BufRead,
+        W: Write,
+        F: FnMut(&mut Scanner<R>, &mut Writer<W>),
+    {
+        let t = scanner.scan::<usize>();
+        for _ in 0..t {
+            solve(scanner, writer);
+        }
+    }
+
+    /// Call `solve` for each case until the input ends.
+    pub fn run_cases_until_eof<R, W, F>(
+        scanner: &mut Scanner<R>,
+        writer: &mut Writer<W>,
+        mut solve: F,
+    ) where
+        R: BufRead,
+        W: Write,
+        F: FnMut(&mut Scanner<R>, &mut Writer<W>),
+    {
+        while scanner.has_next() {
+            solve(scanner, writer);
+        }
+    }
+
+    /// Query and response pair for interactive problems, every query is flushed before reading.
+    pub struct Interactor<R: BufRead, W: Write> {
+        pub scanner: Scanner<R>,
+        pub writer: Writer<W>,
+    }
+
+    impl<'a> Interactor<StdinLock<'a>, StdoutLock<'a>> {
+        pub fn stdio(stdin: &'a Stdin, stdout: &'a Stdout) -> Self {
+            Interactor::new(Scanner::from(stdin), Writer::from(stdout))
+        }
+    }
+
+    impl<R: BufRead, W: Write> Interactor<R, W> {
+        pub fn new(scanner: Scanner<R>, writer: Writer<W>) -> Self {
+            Self { scanner, writer }
+        }
+
+        /// Write `query` as a line, flush, then read the response.
+        pub fn ask<T, Q>(&mut self, query: Q) -> T
+        where
+            T: FromScanner<R>,
+            Q: Display,
+        {
+            self.try_ask(query).unwrap()
+        }
+
+        pub fn try_ask<T, Q>(&mut self, query: Q) -> Result<T>
+        where
+            T: FromScanner<R>,
+            Q: Display,
+        {
+            self.answer(query);
+            T::try_from_scanner(&mut self.scanner)
+        }
+
+        /// Write a line which expects no response and flush.
+        pub fn answer<Q: Display>(&mut self, line: Q) {
+            self.writer.writeln(line);
+            self.writer.flush();
+        }
+    }
+
+    /// `setup!(scanner)` binds a [`Scanner`](crate::contest::cio::Scanner) over locked stdin,
+    /// `setup!(scanner, writer)` also binds a [`Writer`](crate::contest::cio::Writer) over locked stdout
+    /// which flushes on drop.
+    ///
+    /// The handles live in hidden bindings as long as the locks.
+    #[macro_export]
+    macro_rules! setup {
+        ( $scanner:ident ) => {
+            let _stdin = std::io::stdin();
+            let mut $scanner = $crate::contest::cio::Scanner::from(&_stdin);
+        };
+        ( $scanner:ident, $writer:ident ) => {
+            let _stdin = std::io::stdin();
+            let mut $scanner = $crate::contest::cio::Scanner::from(&_stdin);
+            let _stdout = std::io::stdout();
+            let mut $writer = $crate::contest::cio::Writer::from(&_stdout);
+        };
+    }
+    pub use crate::setup;
+}
+
+#[cfg(test)]
+mod test {
+    use super::cio::*;
+
+    /// Only compiled, running it would wait for stdin.
+    #[allow(dead_code)]
+    fn setup_scanner_and_writer() {
+        setup!(scanner, writer);
+        let n = scanner.scan::<usize>();
+        for i in 0..n {
+            writer.writeln(i);
+        }
+    }
+
+    #[test]
+    fn new() {
+        let stdin = std::io::stdin();
+        let _ = Scanner::from(&stdin);
+
+        let input = "1 2 3";
+        let _ = Scanner::from(input);
+    }
+
+    #[test]
     fn scan() {
         let input = "1 -20\nABC 30.1\n";
         let mut scanner = Scanner::from(input);
@@ -283,4 +1222,703 @@ mod test {
             vec![(1, 1), (2, 2), (3, 3),]
         )
     }
+
+    #[test]
+    fn scan_floats_and_wide_integers() {
+        let input = "-0.5 1e9\n1.5 -2.25\n0.5 3\n2.5E-3 7\n";
+        let mut scanner = Scanner::from(input);
+
+        assert_eq!(scanner.scan::<(f64, f64)>(), (-0.5, 1e9));
+        assert_eq!(
+            scanner.scan_n::<(f64, f32)>(3),
+            vec![(1.5, -2.25), (0.5, 3.0), (2.5e-3, 7.0)]
+        );
+
+        let input = "340282366920938463463374607431768211455 -170141183460469231731687303715884105728\n65535 -32768";
+        let mut scanner = Scanner::from(input);
+        assert_eq!(scanner.scan::<(u128, i128)>(), (u128::MAX, i128::MIN));
+        assert_eq!(scanner.scan_n::<(u16, i16)>(1), vec![(u16::MAX, i16::MIN)]);
+        assert!(matches!(
+            Scanner::from("65536").try_parse::<u16>(),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn scan_arrays() {
+        let input = "7\n1 2 3 4 5\n1 2 3 x 4 5\n9 8";
+        let mut scanner = Scanner::from(input);
+
+        let [a]: [usize; 1] = scanner.scan();
+        assert_eq!(a, 7);
+        assert_eq!(scanner.scan::<[i64; 5]>(), [1, 2, 3, 4, 5]);
+        assert_eq!(
+            scanner.scan::<([u8; 3], char, [u8; 2])>(),
+            ([1, 2, 3], 'x', [4, 5])
+        );
+        assert!(matches!(
+            <[i64; 3]>::try_from_scanner(&mut scanner),
+            Err(Error::Eof)
+        ));
+    }
+
+    #[test]
+    fn scan_chars() {
+        let input = "#.#\n..#\n##.\n\n";
+        let mut scanner = Scanner::from(input);
+
+        let grid = scanner.scan_n::<Chars>(3);
+        assert_eq!(grid[0][1], '.');
+        assert_eq!(grid[2][0], '#');
+        assert_eq!(grid[1].len(), 3);
+        assert_eq!(grid[1].iter().filter(|&&c| c == '#').count(), 1);
+        // the empty trailing line is not a row
+        assert!(matches!(
+            Chars::try_from_scanner(&mut scanner),
+            Err(Error::Eof)
+        ));
+    }
+
+    #[test]
+    fn scan_usize1() {
+        let input = "3 2\n1 2\n3 1\n";
+        let mut scanner = Scanner::from(input);
+
+        let (n, m) = scanner.scan::<(usize, usize)>();
+        let edges = scanner.scan_n::<(Usize1, Usize1)>(m);
+        assert_eq!(n, 3);
+        assert_eq!(edges, vec![(Usize1(0), Usize1(1)), (Usize1(2), Usize1(0))]);
+        let (from, to) = edges[1];
+        let (from, to): (usize, usize) = (from.into(), to.into());
+        assert_eq!((from, to), (2, 0));
+
+        let mut scanner = Scanner::from("0");
+        assert!(matches!(
+            Usize1::try_from_scanner(&mut scanner),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn scan_grid() {
+        let input = "3 4\n#..#  \r\n.##.\r\n....\n";
+        let mut scanner = Scanner::from(input);
+
+        let (h, w) = scanner.scan::<(usize, usize)>();
+        let grid = scanner.scan_grid(h, w);
+        assert_eq!(
+            grid,
+            vec![
+                vec!['#', '.', '.', '#'],
+                vec!['.', '#', '#', '.'],
+                vec!['.', '.', '.', '.'],
+            ]
+        );
+
+        let mut scanner = Scanner::from("#..#\n.#.\n");
+        match scanner.try_scan_grid(2, 4) {
+            Err(err @ Error::Parse { .. }) => {
+                assert!(err.to_string().contains("row 1 has width 3"))
+            }
+            other => panic!("{:?}", other),
+        }
+
+        let mut scanner = Scanner::from("#..#\n");
+        assert!(matches!(scanner.try_scan_grid(2, 4), Err(Error::Eof)));
+    }
+
+    #[test]
+    fn scan_matrix() {
+        let input = "2 3\n1 2 3\n4 5 6\n";
+        let mut scanner = Scanner::from(input);
+        let (h, w) = scanner.scan::<(usize, usize)>();
+        assert_eq!(
+            scanner.scan_matrix::<i64>(h, w),
+            vec![vec![1, 2, 3], vec![4, 5, 6]]
+        );
+
+        // rows split over and joined across physical lines
+        let input = "1 2\n3 4 5\n6\n\n7 8 9\n";
+        let mut scanner = Scanner::from(input);
+        assert_eq!(
+            scanner.scan_matrix::<u32>(3, 3),
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]
+        );
+
+        let mut scanner = Scanner::from("1 2 3\n4 5");
+        assert!(matches!(
+            scanner.try_scan_matrix::<i64>(2, 3),
+            Err(Error::Eof)
+        ));
+    }
+
+    #[test]
+    fn scan_line() {
+        let input = "2\nhello world\n3 the rest  of it\r\n\n  indented\n";
+        let mut scanner = Scanner::from(input);
+
+        assert_eq!(scanner.scan::<usize>(), 2);
+        assert_eq!(scanner.scan_line(), "hello world");
+        assert_eq!(scanner.scan::<usize>(), 3);
+        assert_eq!(scanner.scan_line(), "the rest  of it");
+        assert_eq!(scanner.scan_line(), "");
+        assert_eq!(scanner.scan_line(), "  indented");
+        assert!(matches!(scanner.try_scan_line(), Err(Error::Eof)));
+
+        let mut scanner = Scanner::from("a b\nc");
+        assert_eq!(scanner.scan_line(), "a b");
+        assert_eq!(scanner.scan::<String>(), "c");
+    }
+
+    #[test]
+    fn writer() {
+        let mut writer = Writer::new(Vec::new());
+        writer.write(1);
+        writer.write(' ');
+        writer.writeln("two");
+        writer.writeln(format_args!("{} {:.2}", 3, 0.5));
+        writer.flush();
+        assert_eq!(writer.into_inner(), b"1 two\n3 0.50\n");
+    }
+
+    #[test]
+    fn interactor() {
+        use std::cell::RefCell;
+        use std::collections::VecDeque;
+        use std::io::{self, BufReader, Read, Write};
+        use std::rc::Rc;
+
+        /// Lines written by the solver and the judge's pending responses.
+        #[derive(Default)]
+        struct Pipe {
+            query: Vec<u8>,
+            response: VecDeque<u8>,
+            log: Vec<String>,
+        }
+
+        struct ToJudge<F>(Rc<RefCell<Pipe>>, F);
+
+        impl<F: FnMut(&str) -> String> Write for ToJudge<F> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let mut pipe = self.0.borrow_mut();
+                for &b in buf {
+                    if b != b'\n' {
+                        pipe.query.push(b);
+                        continue;
+                    }
+                    let line = String::from_utf8(std::mem::take(&mut pipe.query)).unwrap();
+                    pipe.response.extend((self.1)(&line).bytes());
+                    pipe.log.push(line);
+                }
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        struct FromJudge(Rc<RefCell<Pipe>>);
+
+        impl Read for FromJudge {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                // an unflushed query would leave nothing to read here
+                self.0.borrow_mut().response.read(buf)
+            }
+        }
+
+        let secret = 37;
+        let pipe = Rc::new(RefCell::new(Pipe::default()));
+        let judge = |q: &str| match q.split_once(' ') {
+            Some(("?", m)) if secret >= m.parse::<u32>().unwrap() => "yes\n".to_owned(),
+            Some(("?", _)) => "no\n".to_owned(),
+            _ => String::new(),
+        };
+        let mut interactor = Interactor::new(
+            Scanner::new(BufReader::new(FromJudge(pipe.clone()))),
+            Writer::new(ToJudge(pipe.clone(), judge)),
+        );
+
+        // largest x in 1..=100 with x <= secret
+        let (mut ok, mut ng) = (1, 101);
+        while ng - ok > 1 {
+            let mid = (ok + ng) / 2;
+            match interactor
+                .ask::<String, _>(format_args!("? {}", mid))
+                .as_str()
+            {
+                "yes" => ok = mid,
+                _ => ng = mid,
+            }
+        }
+        interactor.answer(format_args!("! {}", ok));
+
+        let log = &pipe.borrow().log;
+        assert_eq!(log.last().unwrap(), "! 37");
+        assert_eq!(log.len(), 8);
+    }
+
+    #[test]
+    fn preload() {
+        let n = 1_000_000;
+        let mut input = format!("{}\n", n);
+        for i in 0..n {
+            input.push_str(&(i * 7 % 1000).to_string());
+            input.push(if i % 10 == 9 { '\n' } else { ' ' });
+        }
+        let mut scanner = Scanner::preload(input.as_bytes());
+        assert_eq!(scanner.scan::<usize>(), n);
+        let v = scanner.scan_n::<u32>(n);
+        assert!(v
+            .iter()
+            .enumerate()
+            .all(|(i, &x)| x as usize == i * 7 % 1000));
+        assert!(matches!(scanner.try_parse::<u32>(), Err(Error::Eof)));
+
+        let mut scanner = Scanner::preload("2 a b\r\nnext line\n\nc".as_bytes());
+        assert_eq!(scanner.scan::<usize>(), 2);
+        assert_eq!(scanner.scan_line(), "a b");
+        assert_eq!(scanner.scan_line(), "next line");
+        assert_eq!(scanner.scan_line(), "");
+        assert_eq!(scanner.scan::<char>(), 'c');
+        assert!(matches!(scanner.try_scan_line(), Err(Error::Eof)));
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let input = "1 2\n3\n4 abc 5\n";
+        let mut scanner = Scanner::from(input);
+        assert_eq!(scanner.scan_n::<i64>(4), vec![1, 2, 3, 4]);
+        let err = scanner.try_parse::<i64>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to parse \"abc\" as i64 at line 3, token 2: ParseIntError { kind: InvalidDigit }"
+        );
+
+        let mut scanner = Scanner::preload("1\n\n 0".as_bytes());
+        let err = <(Usize1, Usize1)>::try_from_scanner(&mut scanner).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "failed to parse \"0\" as atcoder_lib::contest::cio::Usize1 at line 3, token 1"
+        ));
+    }
+
+    #[test]
+    fn crlf_and_tabs() {
+        let input = "1\t2\r\n3 4\r\nabc\tde\r\n";
+        let mut scanner = Scanner::from(input);
+        assert_eq!(scanner.scan::<(i64, i64)>(), (1, 2));
+        assert_eq!(scanner.scan_n::<u8>(2), vec![3, 4]);
+        assert_eq!(
+            scanner.scan::<(String, String)>(),
+            ("abc".to_owned(), "de".to_owned())
+        );
+        assert!(matches!(scanner.try_parse::<String>(), Err(Error::Eof)));
+
+        let mut scanner = Scanner::preload(input.as_bytes());
+        assert_eq!(scanner.scan_n::<i64>(4), vec![1, 2, 3, 4]);
+        assert_eq!(scanner.scan_line(), "abc\tde");
+        assert!(matches!(scanner.try_parse::<i64>(), Err(Error::Eof)));
+    }
+
+    #[test]
+    fn from_path() {
+        let input = "3 abc\r\n-1 2.5\n\nlast line\n";
+        let path =
+            std::env::temp_dir().join(format!("atcoder_lib_scanner_{}.txt", std::process::id()));
+        std::fs::write(&path, input).unwrap();
+
+        let mut file = Scanner::from_path(&path).unwrap();
+        let mut string = Scanner::from(input);
+        assert_eq!(
+            file.scan::<(usize, String, i64, f64)>(),
+            string.scan::<(usize, String, i64, f64)>()
+        );
+        assert_eq!(file.scan_line(), string.scan_line());
+        assert_eq!(file.scan_line(), "last line");
+        assert_eq!(string.scan_line(), "last line");
+        assert!(matches!(file.try_parse::<String>(), Err(Error::Eof)));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(Scanner::from_path(&path).is_err());
+    }
+
+    #[test]
+    fn scan_cols() {
+        let input = "1 10\n2 20\n3 30\n";
+        let mut scanner = Scanner::from(input);
+        let (a, b) = scanner.scan_cols2::<i64, u32>(3);
+        assert_eq!(a, vec![1, 2, 3]);
+        assert_eq!(b, vec![10, 20, 30]);
+
+        let input = "1 a 2\nb 3 c\n";
+        let mut scanner = Scanner::from(input);
+        let (x, c, y) = scanner.scan_cols3::<u8, char, u8>(1);
+        assert_eq!((x, c, y), (vec![1], vec!['a'], vec![2]));
+        assert_eq!(scanner.scan::<char>(), 'b');
+
+        let (a, b) = Scanner::from("").scan_cols2::<i64, i64>(0);
+        assert_eq!((a.capacity(), b.capacity()), (0, 0));
+
+        let mut scanner = Scanner::from("1 2 3 4 5\n");
+        assert!(matches!(
+            scanner.try_scan_cols3::<i64, i64, i64>(2),
+            Err(Error::Eof)
+        ));
+    }
+
+    #[test]
+    fn has_next() {
+        for input in ["1 2\n3\n", "1 2\n3", "1 2\n3  \n\n \n\n", "\n1\n2 3  "] {
+            let mut scanner = Scanner::from(input);
+            let mut sum = 0;
+            while scanner.has_next() {
+                sum += scanner.scan::<i64>();
+            }
+            assert_eq!(sum, 6, "{:?}", input);
+
+            let mut scanner = Scanner::preload(input.as_bytes());
+            assert_eq!(scanner.scan_n::<i64>(3), vec![1, 2, 3]);
+            assert!(!scanner.has_next());
+        }
+        assert!(!Scanner::from("").has_next());
+    }
+
+    #[test]
+    fn peek() {
+        let input = "2\n1 10 20\n2 3\n";
+        let mut scanner = Scanner::from(input);
+        let q = scanner.scan::<usize>();
+        let mut queries = vec![];
+        for _ in 0..q {
+            // peeking at the next line keeps it for the real scan
+            let token = scanner.peek_token().unwrap().to_owned();
+            assert_eq!(scanner.peek::<String>().unwrap(), token);
+            match scanner.peek::<u8>().unwrap() {
+                1 => queries.push(scanner.scan::<[u32; 3]>().to_vec()),
+                _ => queries.push(scanner.scan::<[u32; 2]>().to_vec()),
+            }
+        }
+        assert_eq!(queries, vec![vec![1, 10, 20], vec![2, 3]]);
+        assert!(matches!(scanner.peek::<u8>(), Err(Error::Eof)));
+
+        let mut scanner = Scanner::preload("x 5\n".as_bytes());
+        assert!(matches!(scanner.peek::<i64>(), Err(Error::Parse { .. })));
+        assert_eq!(scanner.peek_token().unwrap(), "x");
+        assert_eq!(scanner.scan::<(char, i64)>(), ('x', 5));
+    }
+
+    #[test]
+    fn skip() {
+        let mut scanner = Scanner::from("5 label\n7 8 9\n10 junk junk\n11\n");
+        scanner.skip(2);
+        assert_eq!(scanner.scan::<i64>(), 7);
+        scanner.skip_line();
+        assert_eq!(scanner.scan::<i64>(), 10);
+        scanner.skip_line();
+        scanner.skip_line();
+        assert!(matches!(scanner.try_skip_line(), Err(Error::Eof)));
+        assert!(matches!(scanner.try_skip(1), Err(Error::Eof)));
+
+        let mut scanner = Scanner::preload("a b\nc d\n".as_bytes());
+        scanner.skip(1);
+        scanner.skip_line();
+        assert_eq!(scanner.scan::<char>(), 'c');
+        assert!(matches!(scanner.try_skip(2), Err(Error::Eof)));
+    }
+
+    #[test]
+    fn scan_with() {
+        let input = "3 2\n1 2\n3 1\n";
+        let mut scanner = Scanner::from(input);
+        let n = scanner.scan_with(|n: usize| n * 10);
+        assert_eq!(n, 30);
+        let m = scanner.scan::<usize>();
+        let edges = scanner.scan_n_with(m, |(u, v): (usize, usize)| (u - 1, v - 1));
+        assert_eq!(edges, vec![(0, 1), (2, 0)]);
+
+        let mut scanner = Scanner::from("3 a 1 b 2 c");
+        let mut seen = 0;
+        let pairs = scanner.scan_n_with::<(usize, char), _, _>(3, |(i, c)| {
+            seen += 1;
+            (i * seen, c.to_ascii_uppercase())
+        });
+        assert_eq!(pairs, vec![(3, 'A'), (2, 'B'), (6, 'C')]);
+    }
+
+    #[test]
+    fn scan_bytes() {
+        let input = "2\nACGT\n3 GATTACA\n";
+        let mut scanner = Scanner::from(input);
+        assert_eq!(scanner.scan::<usize>(), 2);
+        let dna = scanner.scan::<Bytes>();
+        assert_eq!(dna[0], b'A');
+        assert_eq!(dna.iter().filter(|&&b| b == b'G').count(), 1);
+        assert_eq!(
+            scanner.scan::<(usize, Bytes)>(),
+            (3, Bytes(b"GATTACA".to_vec()))
+        );
+        assert!(matches!(scanner.next_token_bytes(), Err(Error::Eof)));
+
+        // invalid UTF-8 is fine as bytes
+        let mut scanner = Scanner::preload(&b"\xff\xfe 1"[..]);
+        assert_eq!(scanner.next_token_bytes().unwrap(), b"\xff\xfe");
+        assert_eq!(scanner.scan::<u8>(), 1);
+    }
+
+    #[test]
+    fn iter() {
+        let mut scanner = Scanner::from("1 2\n3  \n\n  \n");
+        assert_eq!(scanner.iter::<i64>().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let mut scanner = Scanner::from("2\n1 a\n2 b");
+        scanner.skip(1);
+        assert_eq!(scanner.scan_all::<(u8, char)>(), vec![(1, 'a'), (2, 'b')]);
+
+        assert_eq!(Scanner::from("").iter::<i64>().count(), 0);
+        assert!(Scanner::preload("\n \n".as_bytes())
+            .scan_all::<i64>()
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse \"x\" as i64 at line 2, token 1")]
+    fn iter_parse_error() {
+        let mut scanner = Scanner::from("1\nx 2\n");
+        let _ = scanner.scan_all::<i64>();
+    }
+
+    #[test]
+    fn run_cases() {
+        use std::io::Cursor;
+
+        /// Sum of each case's values.
+        fn solve<R: std::io::BufRead, W: std::io::Write>(s: &mut Scanner<R>, w: &mut Writer<W>) {
+            let n = s.scan::<usize>();
+            w.writeln(s.scan_n::<i64>(n).into_iter().sum::<i64>());
+        }
+
+        let mut scanner = Scanner::new(Cursor::new("3\n2\n1 2\n1\n-5\n3\n1 1 1\n"));
+        let mut writer = Writer::new(Vec::new());
+        super::cio::run_cases(&mut scanner, &mut writer, solve);
+        assert_eq!(writer.into_inner(), b"3\n-5\n3\n");
+
+        let mut scanner = Scanner::from("2\n1 2\n1\n-5\n\n");
+        let mut writer = Writer::new(Vec::new());
+        run_cases_until_eof(&mut scanner, &mut writer, solve);
+        assert_eq!(writer.into_inner(), b"3\n-5\n");
+    }
+
+    #[test]
+    fn yes_no() {
+        let mut scanner = Scanner::from("Yes No YES NO 1 0\nmaybe");
+        let flags = scanner.scan_n::<YesNo>(6);
+        assert_eq!(
+            flags.iter().map(|f| f.0).collect::<Vec<_>>(),
+            vec![true, false, true, false, true, false]
+        );
+        let mut writer = Writer::new(Vec::new());
+        for flag in &flags {
+            writer.write(flag);
+            writer.write(' ');
+        }
+        writer.yes_no(true);
+        assert_eq!(writer.into_inner(), b"Yes No Yes No Yes No Yes\n");
+
+        let err = YesNo::try_from_scanner(&mut scanner).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "failed to parse \"maybe\" as {} at line 2, token 1: expected Yes or No",
+                std::any::type_name::<YesNo>()
+            )
+        );
+    }
+
+    #[test]
+    fn scan_hex_and_bin() {
+        let bits = "1".repeat(100);
+        let input = format!("ff 0xFF 1010 0b11 fg\n{}", bits);
+        let mut scanner = Scanner::from(input.as_str());
+        assert_eq!(scanner.scan::<Hex<u64>>(), Hex(255));
+        assert_eq!(scanner.scan::<Hex<u8>>(), Hex(255));
+        assert_eq!(scanner.scan::<(Bin<u32>, Bin<u32>)>(), (Bin(10), Bin(3)));
+
+        let err = Hex::<u64>::try_from_scanner(&mut scanner).unwrap_err();
+        assert!(
+            err.to_string().starts_with("failed to parse \"fg\" as u64"),
+            "{}",
+            err
+        );
+
+        assert_eq!(scanner.scan::<Bin<u128>>(), Bin((1 << 100) - 1));
+    }
+
+    #[test]
+    fn scan_int_matches_from_str() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(18);
+        let mut tokens = vec![
+            i64::MIN.to_string(),
+            i64::MAX.to_string(),
+            "-9223372036854775809".to_owned(),
+            "9223372036854775808".to_owned(),
+            "18446744073709551615".to_owned(),
+            "18446744073709551616".to_owned(),
+            "-0".to_owned(),
+            "+7".to_owned(),
+            "-".to_owned(),
+            "+".to_owned(),
+            "1-2".to_owned(),
+            "１".to_owned(),
+        ];
+        for _ in 0..2000 {
+            let token = match rng.gen_index(3) {
+                0 => (rng.next_u64() as i64).to_string(),
+                1 => (rng.next_u64() >> rng.gen_range(0..64)).to_string(),
+                _ => (0..1 + rng.gen_index(4))
+                    .map(|_| b"0123456789-+a"[rng.gen_index(13)] as char)
+                    .collect(),
+            };
+            tokens.push(token);
+        }
+
+        for token in &tokens {
+            let mut fast = Scanner::from(token.as_str());
+            let mut generic = Scanner::from(token.as_str());
+            match (i64::try_from_scanner(&mut fast), generic.try_parse::<i64>()) {
+                (Ok(a), Ok(b)) => assert_eq!(a, b, "{}", token),
+                (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string()),
+                (a, b) => panic!("{}: {:?} {:?}", token, a, b),
+            }
+
+            let mut fast = Scanner::from(token.as_str());
+            let mut generic = Scanner::from(token.as_str());
+            match (u64::try_from_scanner(&mut fast), generic.try_parse::<u64>()) {
+                (Ok(a), Ok(b)) => assert_eq!(a, b, "{}", token),
+                (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string()),
+                (a, b) => panic!("{}: {:?} {:?}", token, a, b),
+            }
+        }
+
+        // the fast path across a line of tokens
+        let input = tokens.join(" ");
+        let mut fast = Scanner::from(input.as_str());
+        let mut generic = Scanner::from(input.as_str());
+        for _ in &tokens {
+            assert_eq!(
+                i128::try_from_scanner(&mut fast).ok(),
+                generic.try_parse::<i128>().ok()
+            );
+        }
+    }
+
+    #[test]
+    fn try_scan() {
+        let mut scanner = Scanner::from("1 2\n3");
+        assert_eq!(scanner.try_scan::<(u8, u8)>().unwrap(), (1, 2));
+        assert!(matches!(scanner.try_scan::<(u8, u8)>(), Err(Error::Eof)));
+
+        let mut scanner = Scanner::from("1 2 x 4\n");
+        match scanner.try_scan_n::<i64>(4) {
+            Err(err @ Error::Parse { .. }) => {
+                assert!(err
+                    .to_string()
+                    .starts_with("failed to parse \"x\" as i64 at line 1, token 3"))
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(scanner.try_scan_n::<i64>(1).unwrap(), vec![4]);
+        assert!(matches!(scanner.try_scan_n::<i64>(1), Err(Error::Eof)));
+    }
+
+    #[test]
+    fn global_read() {
+        fn read_edges() -> Vec<(Usize1, Usize1)> {
+            let m = read::<usize>();
+            read_n(m)
+        }
+
+        fn read_header() -> (usize, String) {
+            (read(), read())
+        }
+
+        set_input("3 graph\n2\n1 2\n2 3\n");
+        assert_eq!(read_header(), (3, "graph".to_owned()));
+        assert_eq!(
+            read_edges(),
+            vec![(Usize1(0), Usize1(1)), (Usize1(1), Usize1(2))]
+        );
+
+        set_input("7");
+        assert_eq!(read::<i64>(), 7);
+    }
+
+    #[test]
+    fn scan_extend() {
+        let mut scanner = Scanner::from("2\n3 1 2 3\n2 4 5\n3 7 8 9\n");
+        let q = scanner.scan::<usize>();
+        let mut values = vec![];
+        for _ in 0..q {
+            let k = scanner.scan::<usize>();
+            scanner.scan_extend::<i64>(k, &mut values);
+        }
+        assert_eq!(values, vec![1, 2, 3, 4, 5]);
+
+        let mut buf = [0u32; 3];
+        let k = scanner.scan::<usize>();
+        scanner.scan_into(k, &mut buf);
+        assert_eq!(buf, [7, 8, 9]);
+
+        let mut small = [0u32; 2];
+        assert!(matches!(
+            Scanner::from("1 2 3").try_scan_into(3, &mut small),
+            Err(Error::Length {
+                expected: 3,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn writer_join() {
+        let output = |f: &dyn Fn(&mut Writer<Vec<u8>>)| {
+            let mut writer = Writer::new(Vec::new());
+            f(&mut writer);
+            String::from_utf8(writer.into_inner()).unwrap()
+        };
+        let empty: [i64; 0] = [];
+        let five = [1, -2, 3, 4, 5];
+
+        assert_eq!(output(&|w| w.write_join(&empty, " ")), "");
+        assert_eq!(output(&|w| w.write_join(&[7], " ")), "7");
+        assert_eq!(output(&|w| w.write_join(&five, ", ")), "1, -2, 3, 4, 5");
+        assert_eq!(output(&|w| w.writeln_join(&empty, " ")), "\n");
+        assert_eq!(output(&|w| w.writeln_join(&five, " ")), "1 -2 3 4 5\n");
+        assert_eq!(output(&|w| w.writeln_each(&empty)), "");
+        assert_eq!(output(&|w| w.writeln_each(&[7])), "7\n");
+        assert_eq!(output(&|w| w.writeln_each(&five)), "1\n-2\n3\n4\n5\n");
+        assert_eq!(output(&|w| w.write_pair(3, "x")), "3 x\n");
+    }
+
+    #[test]
+    fn writer_float() {
+        let mut writer = Writer::new(Vec::new());
+        writer.float6(0.1 + 0.2);
+        writer.write(' ');
+        writer.float(-2.5, 3);
+        writer.write(' ');
+        writer.float6(-0.0);
+        writer.write(' ');
+        writer.float(-1e-9, 6);
+        writer.write(' ');
+        writer.float(1e20, 1);
+        writer.writeln("");
+        writer.yes_no(false);
+        writer.writeln_join(&[Fixed(1.0, 2), Fixed(-1.0 / 3.0, 2)], " ");
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "0.300000 -2.500 0.000000 0.000000 100000000000000000000.0\nNo\n1.00 -0.33\n"
+        );
+    }
 }
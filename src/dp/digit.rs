@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Convert a decimal string into its digits, most significant first.
+pub fn decimal_digits(n: &str) -> Vec<u8> {
+    n.bytes()
+        .map(|b| {
+            assert!(b.is_ascii_digit(), "not a decimal digit: {:?}", b as char);
+            b - b'0'
+        })
+        .collect()
+}
+
+/// Count `x` in `0..=N` weighted by `accept` of the state reached by the digits of `x`.
+///
+/// `n_as_digits` is `N` in `base`, most significant first.
+/// `transition(state, digit, leading_zero)` returns the next state, or `None` to prune.
+/// `leading_zero` is `true` while the digit is a zero before the first non-zero digit,
+/// so `x` is always handled as having exactly `n_as_digits.len()` digits.
+///
+/// The tight flag and the started flag are managed internally and states are merged
+/// per position, so the cost is `O(len * base * distinct states)`.
+pub fn digit_dp<S, T, A>(n_as_digits: &[u8], base: u8, init: S, transition: T, accept: A) -> u64
+where
+    S: Clone + Eq + Hash,
+    T: Fn(&S, u8, bool) -> Option<S>,
+    A: Fn(&S) -> u64,
+{
+    debug_assert!(n_as_digits.iter().all(|&d| d < base));
+
+    // (state, tight, started) => number of prefixes
+    let mut dp = HashMap::new();
+    dp.insert((init, true, false), 1u64);
+
+    for &limit in n_as_digits {
+        let mut next = HashMap::with_capacity(dp.len());
+        for ((state, tight, started), ways) in dp {
+            let upper = if tight { limit } else { base - 1 };
+            for d in 0..=upper {
+                let leading_zero = !started && d == 0;
+                if let Some(s) = transition(&state, d, leading_zero) {
+                    *next
+                        .entry((s, tight && d == limit, !leading_zero))
+                        .or_insert(0) += ways;
+                }
+            }
+        }
+        dp = next;
+    }
+
+    dp.into_iter()
+        .map(|((state, _, _), ways)| ways * accept(&state))
+        .sum()
+}
@@ -0,0 +1,88 @@
+use super::digit::{decimal_digits, digit_dp};
+
+fn brute_digits(n: u64, f: impl Fn(&[u8]) -> bool) -> u64 {
+    (0..=n)
+        .filter(|x| f(&decimal_digits(&x.to_string())))
+        .count() as u64
+}
+
+#[test]
+fn decimal_digits_test() {
+    assert_eq!(decimal_digits("1024"), vec![1, 0, 2, 4]);
+}
+
+#[test]
+fn contains_digit() {
+    // count x <= n containing 3, zeros are fine as the target is non-zero.
+    let count = |n: u64| {
+        digit_dp(
+            &decimal_digits(&n.to_string()),
+            10,
+            false,
+            |&found, d, _| Some(found || d == 3),
+            |&found| found as u64,
+        )
+    };
+
+    for n in (0..=2000).chain([54_321, 100_000]) {
+        assert_eq!(count(n), brute_digits(n, |ds| ds.contains(&3)), "n = {}", n);
+    }
+}
+
+#[test]
+fn contains_zero_ignores_leading_zeros() {
+    let count = |n: u64| {
+        digit_dp(
+            &decimal_digits(&n.to_string()),
+            10,
+            false,
+            |&found, d, leading_zero| Some(found || (d == 0 && !leading_zero)),
+            |&found| found as u64,
+        )
+    };
+
+    // "0" itself only consists of a leading zero
+    let brute = |n| brute_digits(n, |ds| ds.len() > 1 && ds.contains(&0));
+    for n in (0..=2000).chain([100_000]) {
+        assert_eq!(count(n), brute(n), "n = {}", n);
+    }
+}
+
+#[test]
+fn digit_sum_divisible() {
+    let k = 7;
+    let count = |n: u64| {
+        digit_dp(
+            &decimal_digits(&n.to_string()),
+            10,
+            0u8,
+            |&rem, d, _| Some((rem + d) % k),
+            |&rem| (rem == 0) as u64,
+        )
+    };
+
+    let brute = |n| {
+        brute_digits(n, |ds| {
+            ds.iter().map(|&d| d as u32).sum::<u32>() % k as u32 == 0
+        })
+    };
+    for n in (0..=2000).chain([99_999, 100_000]) {
+        assert_eq!(count(n), brute(n), "n = {}", n);
+    }
+}
+
+#[test]
+fn binary() {
+    // popcount == 2 for x <= 0b1011
+    let count = digit_dp(
+        &[1, 0, 1, 1],
+        2,
+        0u8,
+        |&ones, d, _| if ones + d > 2 { None } else { Some(ones + d) },
+        |&ones| (ones == 2) as u64,
+    );
+    assert_eq!(
+        count,
+        (0..=0b1011u32).filter(|x| x.count_ones() == 2).count() as u64
+    );
+}
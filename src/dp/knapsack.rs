@@ -0,0 +1,82 @@
+/// 0/1 knapsack. `items` are `(weight, value)`.
+///
+/// Returns `dp` where `dp[c]` is the best total value with total weight at most `c`.
+pub fn knapsack_01(items: &[(u64, i64)], capacity: usize) -> Vec<i64> {
+    let mut dp = vec![0i64; capacity + 1];
+    for &(w, v) in items {
+        if w > capacity as u64 {
+            continue;
+        }
+        let w = w as usize;
+        // backward so each item is used at most once
+        for c in (w..=capacity).rev() {
+            dp[c] = dp[c].max(dp[c - w].saturating_add(v));
+        }
+    }
+    dp
+}
+
+/// Knapsack where every item can be used any number of times.
+pub fn knapsack_unbounded(items: &[(u64, i64)], capacity: usize) -> Vec<i64> {
+    let mut dp = vec![0i64; capacity + 1];
+    for &(w, v) in items {
+        if w > capacity as u64 {
+            continue;
+        }
+        let w = w as usize;
+        if w == 0 {
+            // only a non-positive zero weight item keeps the answer bounded
+            assert!(v <= 0, "zero weight item with positive value is unbounded");
+            continue;
+        }
+        // forward so an item can be reused
+        for c in w..=capacity {
+            dp[c] = dp[c].max(dp[c - w].saturating_add(v));
+        }
+    }
+    dp
+}
+
+/// Knapsack where the item `(weight, value, count)` can be used up to `count` times.
+///
+/// Each item is split into `1, 2, 4, ..` sized bundles and solved as 0/1 knapsack.
+pub fn knapsack_bounded(items: &[(u64, i64, u64)], capacity: usize) -> Vec<i64> {
+    let mut bundles = Vec::new();
+    for &(w, v, mut count) in items {
+        let mut k = 1;
+        while count > 0 {
+            let take = k.min(count);
+            bundles.push((w.saturating_mul(take), v.saturating_mul(take as i64)));
+            count -= take;
+            k *= 2;
+        }
+    }
+    knapsack_01(&bundles, capacity)
+}
+
+/// 0/1 knapsack for huge `capacity` and small total value.
+///
+/// Runs the dual DP "minimum weight to reach exactly value `v`" in `O(n * sum(value))`
+/// and returns the best total value with total weight at most `capacity`.
+/// Values must be non-negative.
+pub fn knapsack_01_by_value(items: &[(u64, i64)], capacity: u64) -> i64 {
+    assert!(
+        items.iter().all(|&(_, v)| v >= 0),
+        "values must be non-negative"
+    );
+    let total = items.iter().map(|&(_, v)| v as usize).sum::<usize>();
+
+    // u64::MAX marks unreachable values
+    let mut dp = vec![u64::MAX; total + 1];
+    dp[0] = 0;
+    for &(w, v) in items {
+        let v = v as usize;
+        for s in (v..=total).rev() {
+            if dp[s - v] != u64::MAX {
+                dp[s] = dp[s].min(dp[s - v].saturating_add(w));
+            }
+        }
+    }
+
+    (0..=total).rev().find(|&s| dp[s] <= capacity).unwrap_or(0) as i64
+}
@@ -0,0 +1,58 @@
+use super::knapsack::{knapsack_01, knapsack_01_by_value, knapsack_bounded, knapsack_unbounded};
+
+#[test]
+fn knapsack_01_textbook() {
+    let items = [(2, 3), (1, 2), (3, 4), (2, 2)];
+    let dp = knapsack_01(&items, 5);
+    assert_eq!(dp, vec![0, 2, 3, 5, 6, 7]);
+
+    // negative values are never taken
+    assert_eq!(knapsack_01(&[(1, -5)], 3), vec![0; 4]);
+    // too heavy items are ignored
+    assert_eq!(knapsack_01(&[(10, 5)], 3), vec![0; 4]);
+}
+
+#[test]
+fn knapsack_unbounded_textbook() {
+    let items = [(3, 4), (4, 5), (2, 3)];
+    let dp = knapsack_unbounded(&items, 7);
+    assert_eq!(dp[7], 10);
+    assert_eq!(dp[6], 9);
+}
+
+#[test]
+fn knapsack_01_by_value_agrees() {
+    let mut x = 88172645463325252u64;
+    let mut rand = |m: u64| {
+        x ^= x << 7;
+        x ^= x >> 9;
+        x % m
+    };
+    for _ in 0..100 {
+        let n = rand(8) as usize;
+        let items = (0..n)
+            .map(|_| (rand(10) + 1, rand(20) as i64))
+            .collect::<Vec<_>>();
+        let capacity = rand(40) as usize;
+        assert_eq!(
+            knapsack_01_by_value(&items, capacity as u64),
+            knapsack_01(&items, capacity)[capacity]
+        );
+    }
+
+    assert_eq!(
+        knapsack_01_by_value(&[(1 << 40, 7), (1 << 41, 9)], 3 << 40),
+        16
+    );
+}
+
+#[test]
+fn knapsack_bounded_duplicates() {
+    let items = [(3, 5, 4), (2, 3, 7), (5, 9, 1)];
+    let duplicated = items
+        .iter()
+        .flat_map(|&(w, v, c)| std::iter::repeat((w, v)).take(c as usize))
+        .collect::<Vec<_>>();
+
+    assert_eq!(knapsack_bounded(&items, 30), knapsack_01(&duplicated, 30));
+}
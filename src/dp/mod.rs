@@ -0,0 +1,8 @@
+pub mod digit;
+pub mod knapsack;
+
+#[cfg(test)]
+mod digit_test;
+
+#[cfg(test)]
+mod knapsack_test;
@@ -0,0 +1,178 @@
+//! Searches shared by every graph representation through [`Adjacency`].
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use super::cost::Weight;
+
+/// Outgoing edges of each vertex, implemented by [`Dag`](super::Dag),
+/// [`Graph`](super::Graph) and [`Csr`](super::Csr).
+pub trait Adjacency {
+    type Weight: Copy;
+
+    /// Number of vertices.
+    fn vertex_count(&self) -> usize;
+
+    /// Call `f(to, cost)` for each edge leaving `v`.
+    fn for_each_neighbor<F: FnMut(usize, Self::Weight)>(&self, v: usize, f: F);
+}
+
+/// Shortest distances from `from` to every vertex, `None` for unreachable ones.
+pub fn dijkstra_all<G>(g: &G, from: usize) -> Vec<Option<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    dijkstra(
+        g,
+        &[(from, G::Weight::ZERO)],
+        None,
+        |_| G::Weight::ZERO,
+        &mut 0,
+    )
+}
+
+/// Number of edges on a shortest path from `from` to each vertex ignoring costs, `None` if unreachable.
+pub fn bfs<G: Adjacency>(g: &G, from: usize) -> Vec<Option<u32>> {
+    bfs_parents(g, &[from]).0
+}
+
+/// Dijkstra from `(vertex, initial cost)` sources, distances are final only up to `to` when it is given.
+///
+/// Vertices are popped in order of `distance + h(v)`, which makes it A* search toward `to`
+/// for a consistent estimate `h`, and plain Dijkstra for `|_| 0`.
+/// `popped` is increased by the number of vertices expanded.
+///
+/// Edge costs must not be negative and `h` must be consistent,
+/// `h(v) <= cost + h(u)` for every edge `v -> u` and `h(to) == 0`, which is asserted in debug builds only.
+pub(crate) fn dijkstra<G, H>(
+    g: &G,
+    sources: &[(usize, G::Weight)],
+    to: Option<usize>,
+    h: H,
+    popped: &mut usize,
+) -> Vec<Option<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+    H: Fn(usize) -> G::Weight,
+{
+    if let Some(to) = to {
+        debug_assert_eq!(h(to), G::Weight::ZERO, "heuristic of the goal must be 0");
+    }
+    // None is infinity, and so are sums overflowing W
+    let mut dist = vec![None; g.vertex_count()];
+    // (distance + h, distance, vertex)
+    let mut queue = BinaryHeap::new();
+    for &(s, cost) in sources {
+        if dist[s].map_or(true, |d| cost < d) {
+            dist[s] = Some(cost);
+            queue.push(Reverse((cost.saturating_add(h(s)), cost, s)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, position))) = queue.pop() {
+        if Some(position) == to {
+            break;
+        }
+
+        if dist[position].map_or(false, |d| cost > d) {
+            continue;
+        }
+        *popped += 1;
+
+        g.for_each_neighbor(position, |next_position, edge_cost| {
+            debug_assert!(
+                edge_cost >= G::Weight::ZERO,
+                "negative edge {} -> {} of cost {:?}",
+                position,
+                next_position,
+                edge_cost
+            );
+            debug_assert!(
+                h(position) <= edge_cost.saturating_add(h(next_position)),
+                "heuristic is not consistent on edge {} -> {}",
+                position,
+                next_position
+            );
+            let Some(next) = cost.checked_add(edge_cost) else { return };
+            if dist[next_position].map_or(true, |d| next < d) {
+                queue.push(Reverse((
+                    next.saturating_add(h(next_position)),
+                    next,
+                    next_position,
+                )));
+                dist[next_position] = Some(next);
+            }
+        });
+    }
+
+    dist
+}
+
+/// 0-1 BFS, each cost must be 0 or 1.
+pub(crate) fn bfs01<G: Adjacency<Weight = i64>>(g: &G, from: usize) -> Vec<Option<i64>> {
+    let mut dist = vec![None; g.vertex_count()];
+    dist[from] = Some(0);
+    let mut queue = VecDeque::from([(0, from)]);
+    while let Some((d, v)) = queue.pop_front() {
+        if dist[v].map_or(false, |dv| d > dv) {
+            continue;
+        }
+        g.for_each_neighbor(v, |to, cost| {
+            debug_assert!(cost == 0 || cost == 1, "cost {} is neither 0 nor 1", cost);
+            let next = d + cost;
+            if dist[to].map_or(true, |dt| next < dt) {
+                dist[to] = Some(next);
+                if cost == 0 {
+                    queue.push_front((next, to));
+                } else {
+                    queue.push_back((next, to));
+                }
+            }
+        });
+    }
+    dist
+}
+
+/// BFS distances and parents from all of `sources` at once,
+/// the parent of sources and of unreachable vertices is `usize::MAX`.
+pub(crate) fn bfs_parents<G: Adjacency>(
+    g: &G,
+    sources: &[usize],
+) -> (Vec<Option<u32>>, Vec<usize>) {
+    let n = g.vertex_count();
+    let mut dist = vec![None; n];
+    let mut parent = vec![usize::MAX; n];
+    let mut queue = VecDeque::new();
+    for &s in sources {
+        if dist[s].is_none() {
+            dist[s] = Some(0);
+            queue.push_back(s);
+        }
+    }
+    while let Some(v) = queue.pop_front() {
+        let d = dist[v].unwrap() + 1;
+        g.for_each_neighbor(v, |to, _| {
+            if dist[to].is_none() {
+                dist[to] = Some(d);
+                parent[to] = v;
+                queue.push_back(to);
+            }
+        });
+    }
+    (dist, parent)
+}
+
+/// Walk `parent` back from `to`, `None` if it was not reached.
+pub(crate) fn path_to(dist: &[Option<u32>], parent: &[usize], to: usize) -> Option<Vec<usize>> {
+    dist[to]?;
+    let mut path = vec![to];
+    let mut v = to;
+    while parent[v] != usize::MAX {
+        v = parent[v];
+        path.push(v);
+    }
+    path.reverse();
+    Some(path)
+}
@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+
+const NONE: usize = usize::MAX;
+
+/// Maximum bipartite matching by Hopcroft-Karp in `O(E sqrt(V))`.
+///
+/// Lighter than modeling the matching as a [`MaxFlow`](super::MaxFlow).
+pub struct BipartiteMatching {
+    adj: Vec<Vec<usize>>,
+    pair_left: Vec<usize>,
+    pair_right: Vec<usize>,
+}
+
+impl BipartiteMatching {
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            adj: vec![vec![]; left],
+            pair_left: vec![NONE; left],
+            pair_right: vec![NONE; right],
+        }
+    }
+
+    pub fn add_edge(&mut self, l: usize, r: usize) {
+        assert!(r < self.pair_right.len());
+        self.adj[l].push(r);
+    }
+
+    /// Maximum matching as `(l, r)` pairs in ascending order of `l`.
+    ///
+    /// Edges added afterwards are taken into account by calling it again.
+    pub fn matching(&mut self) -> Vec<(usize, usize)> {
+        while self.augment() {}
+        self.pair_left
+            .iter()
+            .enumerate()
+            .filter(|&(_, &r)| r != NONE)
+            .map(|(l, &r)| (l, r))
+            .collect()
+    }
+
+    /// Right vertex matched with `l` by the last [`BipartiteMatching::matching`].
+    pub fn pair_of_left(&self, l: usize) -> Option<usize> {
+        Some(self.pair_left[l]).filter(|&r| r != NONE)
+    }
+
+    /// Left vertex matched with `r` by the last [`BipartiteMatching::matching`].
+    pub fn pair_of_right(&self, r: usize) -> Option<usize> {
+        Some(self.pair_right[r]).filter(|&l| l != NONE)
+    }
+
+    /// One phase, augmenting along a maximal set of disjoint shortest paths.
+    /// Returns `false` if no augmenting path exists.
+    fn augment(&mut self) -> bool {
+        let n = self.adj.len();
+        let mut dist = vec![NONE; n];
+        let mut queue = (0..n)
+            .filter(|&l| self.pair_left[l] == NONE)
+            .collect::<VecDeque<_>>();
+        queue.iter().for_each(|&l| dist[l] = 0);
+        let mut found = false;
+        while let Some(l) = queue.pop_front() {
+            for &r in &self.adj[l] {
+                match self.pair_right[r] {
+                    NONE => found = true,
+                    w if dist[w] == NONE => {
+                        dist[w] = dist[l] + 1;
+                        queue.push_back(w);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !found {
+            return false;
+        }
+
+        // iterative dfs along the layers, stack[i] tries the edge adj[stack[i]][iter[stack[i]]]
+        let mut iter = vec![0; n];
+        for root in 0..n {
+            if self.pair_left[root] != NONE {
+                continue;
+            }
+            let mut stack = vec![root];
+            while let Some(&l) = stack.last() {
+                let Some(&r) = self.adj[l].get(iter[l]) else {
+                    // dead end for this phase
+                    dist[l] = NONE;
+                    stack.pop();
+                    if let Some(&p) = stack.last() {
+                        iter[p] += 1;
+                    }
+                    continue;
+                };
+                match self.pair_right[r] {
+                    NONE => {
+                        for &u in &stack {
+                            let r = self.adj[u][iter[u]];
+                            self.pair_left[u] = r;
+                            self.pair_right[r] = u;
+                        }
+                        break;
+                    }
+                    w if dist[w] != NONE && dist[w] == dist[l] + 1 => stack.push(w),
+                    _ => iter[l] += 1,
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::rand::XorShift64;
+
+    fn assert_matching(edges: &[(usize, usize)], matching: &[(usize, usize)]) {
+        let mut left = std::collections::HashSet::new();
+        let mut right = std::collections::HashSet::new();
+        for &(l, r) in matching {
+            assert!(edges.contains(&(l, r)));
+            assert!(left.insert(l) && right.insert(r));
+        }
+    }
+
+    #[test]
+    fn perfect() {
+        let edges = [(0, 0), (0, 1), (1, 0), (2, 1), (2, 2), (1, 2)];
+        let mut m = BipartiteMatching::new(3, 3);
+        for &(l, r) in &edges {
+            m.add_edge(l, r);
+        }
+        let matching = m.matching();
+        assert_eq!(matching.len(), 3);
+        assert_matching(&edges, &matching);
+        for (l, r) in matching {
+            assert_eq!(m.pair_of_left(l), Some(r));
+            assert_eq!(m.pair_of_right(r), Some(l));
+        }
+    }
+
+    #[test]
+    fn smaller_than_both_sides() {
+        // left 0, 1, 2 only know right 0, and left 3 knows right 1 and 2
+        let edges = [(0, 0), (1, 0), (2, 0), (3, 1), (3, 2)];
+        let mut m = BipartiteMatching::new(4, 4);
+        for &(l, r) in &edges {
+            m.add_edge(l, r);
+        }
+        let matching = m.matching();
+        assert_eq!(matching.len(), 2);
+        assert_matching(&edges, &matching);
+        assert_eq!((0..3).filter_map(|l| m.pair_of_left(l)).count(), 1);
+        assert_eq!(m.pair_of_right(3), None);
+
+        // a new edge extends the previous matching
+        m.add_edge(1, 3);
+        assert_eq!(m.matching().len(), 3);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        fn brute(l: usize, left: usize, used: u32, adj: &[Vec<usize>]) -> usize {
+            if l == left {
+                return 0;
+            }
+            let mut best = brute(l + 1, left, used, adj);
+            for &r in &adj[l] {
+                if used >> r & 1 == 0 {
+                    best = best.max(1 + brute(l + 1, left, used | 1 << r, adj));
+                }
+            }
+            best
+        }
+
+        let mut rng = XorShift64::new(23);
+        for _ in 0..300 {
+            let (left, right) = (1 + rng.gen_index(7), 1 + rng.gen_index(7));
+            let edges = (0..rng.gen_index(left * right + 1))
+                .map(|_| (rng.gen_index(left), rng.gen_index(right)))
+                .collect::<Vec<_>>();
+            let mut adj = vec![vec![]; left];
+            let mut m = BipartiteMatching::new(left, right);
+            for &(l, r) in &edges {
+                adj[l].push(r);
+                m.add_edge(l, r);
+            }
+            let matching = m.matching();
+            assert_matching(&edges, &matching);
+            assert_eq!(matching.len(), brute(0, left, 0, &adj));
+        }
+    }
+
+    #[test]
+    fn long_augmenting_path() {
+        // left i knows right i and i + 1, the greedy start leaves a path through everything
+        let n = 100_000;
+        let mut m = BipartiteMatching::new(n, n);
+        for i in 0..n {
+            if i + 1 < n {
+                m.add_edge(i, i + 1);
+            }
+            m.add_edge(i, i);
+        }
+        assert_eq!(m.matching().len(), n);
+    }
+}
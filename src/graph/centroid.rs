@@ -0,0 +1,203 @@
+use super::tree::Tree;
+
+const NONE: usize = usize::MAX;
+
+/// Centroid decomposition of a [`Tree`].
+///
+/// The centroid of the whole tree is removed first, then the centroids of the remaining
+/// components recursively, so every path goes through the first removed centroid on it
+/// and each vertex is in `O(log n)` components.
+#[derive(Clone, Debug)]
+pub struct CentroidDecomposition {
+    adj: Vec<Vec<usize>>,
+    parent: Vec<usize>,
+    level: Vec<usize>,
+    order: Vec<usize>,
+}
+
+impl CentroidDecomposition {
+    pub fn new(tree: &Tree) -> Self {
+        let n = tree.len();
+        let adj = (0..n)
+            .map(|v| tree.neighbors(v).to_vec())
+            .collect::<Vec<_>>();
+        let mut parent = vec![NONE; n];
+        let mut level = vec![NONE; n];
+        let mut order = Vec::with_capacity(n);
+        let mut size = vec![0; n];
+        let mut bfs_parent = vec![NONE; n];
+
+        // (any vertex of a component, centroid it was split from)
+        let mut stack = if n == 0 { vec![] } else { vec![(0, NONE)] };
+        while let Some((start, up)) = stack.pop() {
+            // vertices already removed have a level
+            let mut component = vec![start];
+            bfs_parent[start] = NONE;
+            let mut head = 0;
+            while let Some(&v) = component.get(head) {
+                head += 1;
+                for &u in &adj[v] {
+                    if u != bfs_parent[v] && level[u] == NONE {
+                        bfs_parent[u] = v;
+                        component.push(u);
+                    }
+                }
+            }
+            for &v in component.iter().rev() {
+                size[v] = 1 + adj[v]
+                    .iter()
+                    .filter(|&&u| u != bfs_parent[v] && level[u] == NONE)
+                    .map(|&u| size[u])
+                    .sum::<usize>();
+            }
+
+            // go down into the heavy child while it has more than half
+            let total = component.len();
+            let mut c = start;
+            while let Some(&heavy) = adj[c]
+                .iter()
+                .find(|&&u| u != bfs_parent[c] && level[u] == NONE && size[u] * 2 > total)
+            {
+                c = heavy;
+            }
+
+            parent[c] = up;
+            level[c] = if up == NONE { 0 } else { level[up] + 1 };
+            order.push(c);
+            stack.extend(
+                adj[c]
+                    .iter()
+                    .filter(|&&u| level[u] == NONE)
+                    .map(|&u| (u, c)),
+            );
+        }
+
+        Self {
+            adj,
+            parent,
+            level,
+            order,
+        }
+    }
+
+    /// Centroid whose removal made the component of the centroid `v`, `None` for the first one.
+    pub fn parent_centroid(&self, v: usize) -> Option<usize> {
+        (self.parent[v] != NONE).then_some(self.parent[v])
+    }
+
+    /// Depth of `v` in the centroid tree, which is at most `log2 n`.
+    pub fn level(&self, v: usize) -> usize {
+        self.level[v]
+    }
+
+    /// Vertices in the order they were removed as centroids.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Vertices of the component of the centroid `c` when it was removed, as
+    /// `(vertex, distance from c, neighbor of c on the path)` with `c` itself first as `(c, 0, c)`.
+    ///
+    /// Pairs through `c` are the pairs with different neighbors,
+    /// and the sizes of all components sum to `O(n log n)`.
+    pub fn component(&self, c: usize) -> Vec<(usize, usize, usize)> {
+        let mut component = vec![(c, 0, c)];
+        // (vertex, parent, distance, branch)
+        let mut stack = self.adj[c]
+            .iter()
+            .filter(|&&u| self.level[u] > self.level[c])
+            .map(|&u| (u, c, 1, u))
+            .collect::<Vec<_>>();
+        while let Some((v, p, d, branch)) = stack.pop() {
+            component.push((v, d, branch));
+            for &u in &self.adj[v] {
+                if u != p && self.level[u] > self.level[c] {
+                    stack.push((u, v, d + 1, branch));
+                }
+            }
+        }
+        component
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tree::random_tree;
+    use crate::util::rand::XorShift64;
+
+    /// Unordered pairs with `a + b <= k` of sorted `d`.
+    fn pairs_within(d: &mut [usize], k: usize) -> usize {
+        d.sort_unstable();
+        let mut count = 0;
+        let mut j = d.len();
+        for i in 0..d.len() {
+            while j > 0 && d[i] + d[j - 1] > k {
+                j -= 1;
+            }
+            // partners before i only
+            count += j.min(i);
+        }
+        count
+    }
+
+    #[test]
+    fn path() {
+        let tree = Tree::from_edges(7, &(1..7).map(|v| (v - 1, v)).collect::<Vec<_>>(), 0);
+        let cd = CentroidDecomposition::new(&tree);
+        assert_eq!(cd.order()[0], 3);
+        assert_eq!(cd.parent_centroid(3), None);
+        assert_eq!(cd.parent_centroid(1), Some(3));
+        assert_eq!(cd.parent_centroid(0), Some(1));
+        assert_eq!(cd.level(6), 2);
+
+        let mut component = cd.component(5);
+        component.sort_unstable();
+        assert_eq!(component, vec![(4, 1, 4), (5, 0, 5), (6, 1, 6)]);
+    }
+
+    #[test]
+    fn pairs_within_distance() {
+        let mut rng = XorShift64::new(37);
+        for n in 1..60 {
+            let edges = random_tree(&mut rng, n);
+            let tree = Tree::from_edges(n, &edges, 0);
+            let cd = CentroidDecomposition::new(&tree);
+            let mut order = cd.order().to_vec();
+            order.sort_unstable();
+            assert_eq!(order, (0..n).collect::<Vec<_>>());
+            assert!((0..n).all(|v| 1 << cd.level(v) <= n));
+
+            let k = rng.gen_index(8);
+            let mut count = 0;
+            for c in 0..n {
+                let component = cd.component(c);
+                let mut all = component.iter().map(|&(_, d, _)| d).collect::<Vec<_>>();
+                count += pairs_within(&mut all, k);
+                // pairs within the same branch don't go through c
+                let mut branches = std::collections::BTreeMap::<_, Vec<_>>::new();
+                for &(_, d, b) in &component[1..] {
+                    branches.entry(b).or_default().push(d);
+                }
+                for mut d in branches.into_values() {
+                    count -= pairs_within(&mut d, k);
+                }
+            }
+
+            let brute = (0..n)
+                .flat_map(|u| (0..u).map(move |v| (u, v)))
+                .filter(|&(u, v)| tree.dist(u, v) <= k)
+                .count();
+            assert_eq!(count, brute, "n = {}, k = {}", n, k);
+        }
+    }
+
+    #[test]
+    fn deep_path() {
+        let n = 200_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let cd = CentroidDecomposition::new(&Tree::from_edges(n, &edges, 0));
+        assert!((0..n).all(|v| cd.level(v) < 18));
+        assert_eq!(cd.component(cd.order()[0]).len(), n);
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt::Debug;
+use std::ops::Add;
+
+/// Edge weight of a [`Dag`](super::dag::Dag), sums are checked so overflow reads as infinity.
+pub trait Weight: Copy + Ord + Debug {
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),+) => {
+        $(impl Weight for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$t>::saturating_add(self, rhs)
+            }
+        })+
+    };
+}
+impl_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Path cost with an explicit infinity.
+///
+/// `Infinite` compares greater than any finite cost and absorbs additions,
+/// so relaxing `dist[u] + cost` never overflows.
+/// Finite sums overflowing upward become `Infinite`, downward saturate at `i64::MIN`.
+/// A saturated sum stops decreasing, so negative cycle checks must not rely on it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Cost {
+    Finite(i64),
+    Infinite,
+}
+
+impl Cost {
+    pub fn is_finite(self) -> bool {
+        matches!(self, Cost::Finite(_))
+    }
+
+    /// Returns the finite value, `None` if infinite.
+    pub fn finite(self) -> Option<i64> {
+        match self {
+            Cost::Finite(v) => Some(v),
+            Cost::Infinite => None,
+        }
+    }
+
+    /// Replace self with `other` if `other` is smaller. Returns `true` if updated.
+    pub fn chmin(&mut self, other: Cost) -> bool {
+        if other < *self {
+            *self = other;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, rhs: Cost) -> Cost {
+        match (self, rhs) {
+            (Cost::Finite(a), Cost::Finite(b)) => match a.checked_add(b) {
+                Some(c) => Cost::Finite(c),
+                None if a > 0 => Cost::Infinite,
+                None => Cost::Finite(i64::MIN),
+            },
+            _ => Cost::Infinite,
+        }
+    }
+}
+
+impl Add<i64> for Cost {
+    type Output = Cost;
+
+    fn add(self, rhs: i64) -> Cost {
+        self + Cost::Finite(rhs)
+    }
+}
+
+/// `i64::MAX` is treated as the infinity sentinel.
+impl From<i64> for Cost {
+    fn from(v: i64) -> Self {
+        if v == i64::MAX {
+            Cost::Infinite
+        } else {
+            Cost::Finite(v)
+        }
+    }
+}
+
+/// `Infinite` becomes the `i64::MAX` sentinel.
+impl From<Cost> for i64 {
+    fn from(c: Cost) -> Self {
+        c.finite().unwrap_or(i64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infinite_absorbs() {
+        assert_eq!(Cost::Infinite + 10, Cost::Infinite);
+        assert_eq!(Cost::Finite(-10) + Cost::Infinite, Cost::Infinite);
+        assert_eq!(Cost::Finite(i64::MAX - 1) + 10, Cost::Infinite);
+        assert_eq!(Cost::Finite(i64::MIN) + -1, Cost::Finite(i64::MIN));
+        assert_eq!(Cost::Finite(1) + 2, Cost::Finite(3));
+    }
+
+    #[test]
+    fn ordering() {
+        let mut costs = vec![Cost::Infinite, Cost::Finite(i64::MAX - 1), Cost::Finite(-5)];
+        costs.sort();
+        assert_eq!(
+            costs,
+            vec![Cost::Finite(-5), Cost::Finite(i64::MAX - 1), Cost::Infinite]
+        );
+
+        let mut c = Cost::Infinite;
+        assert!(c.chmin(Cost::Finite(3)));
+        assert!(!c.chmin(Cost::Finite(4)));
+        assert_eq!(c, Cost::Finite(3));
+    }
+
+    #[test]
+    fn convert() {
+        assert_eq!(Cost::from(i64::MAX), Cost::Infinite);
+        assert_eq!(i64::from(Cost::Infinite), i64::MAX);
+        assert_eq!(i64::from(Cost::from(7)), 7);
+        assert!(!Cost::Infinite.is_finite());
+        assert_eq!(Cost::Finite(7).finite(), Some(7));
+    }
+}
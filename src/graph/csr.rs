@@ -0,0 +1,144 @@
+use super::adjacency::{self, Adjacency};
+
+/// Directed graph in compressed sparse row form, built once from an edge list.
+///
+/// The edges leaving `v` are `adj[start[v]..start[v + 1]]` in one flat array,
+/// avoiding the allocation per vertex of [`Dag`](super::Dag) for large inputs.
+#[derive(Clone, Debug)]
+pub struct Csr {
+    start: Vec<usize>,
+    // (to, cost)
+    adj: Vec<(usize, i64)>,
+}
+
+impl Csr {
+    /// Graph of `n` vertices and `(from, to, cost)` edges by a counting sort in `O(n + m)`.
+    ///
+    /// Edges leaving the same vertex keep their order in `edges`.
+    pub fn from_edges(n: usize, edges: &[(usize, usize, i64)]) -> Self {
+        let mut start = vec![0; n + 1];
+        for &(from, _, _) in edges {
+            start[from + 1] += 1;
+        }
+        for v in 0..n {
+            start[v + 1] += start[v];
+        }
+        let mut next = start.clone();
+        let mut adj = vec![(0, 0); edges.len()];
+        for &(from, to, cost) in edges {
+            adj[next[from]] = (to, cost);
+            next[from] += 1;
+        }
+        Self { start, adj }
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    /// `(to, cost)` of the edges leaving `v`.
+    pub fn neighbors(&self, v: usize) -> &[(usize, i64)] {
+        &self.adj[self.start[v]..self.start[v + 1]]
+    }
+
+    /// Shortest distances from `from` to every vertex, `None` for unreachable ones.
+    pub fn dijkstra_all(&self, from: usize) -> Vec<Option<i64>> {
+        adjacency::dijkstra_all(self, from)
+    }
+
+    /// Number of edges on a shortest path from `from` to each vertex ignoring costs, `None` if unreachable.
+    pub fn bfs(&self, from: usize) -> Vec<Option<u32>> {
+        adjacency::bfs(self, from)
+    }
+}
+
+impl Adjacency for Csr {
+    type Weight = i64;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn for_each_neighbor<F: FnMut(usize, i64)>(&self, v: usize, mut f: F) {
+        self.neighbors(v).iter().for_each(|&(to, cost)| f(to, cost));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Dag;
+    use crate::util::rand::XorShift64;
+
+    fn random_edges(rng: &mut XorShift64, n: usize, m: usize) -> Vec<(usize, usize, i64)> {
+        (0..m)
+            .map(|_| {
+                let cost = rng.gen_range(0..100) as i64;
+                (rng.gen_index(n), rng.gen_index(n), cost)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn layout() {
+        let edges = [(2, 0, 4), (0, 1, 1), (2, 1, 5), (0, 2, 2)];
+        let g = Csr::from_edges(4, &edges);
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(0), &[(1, 1), (2, 2)]);
+        assert_eq!(g.neighbors(1), &[]);
+        assert_eq!(g.neighbors(2), &[(0, 4), (1, 5)]);
+        assert_eq!(g.neighbors(3), &[]);
+        assert!(Csr::from_edges(0, &[]).is_empty());
+    }
+
+    #[test]
+    fn matches_dag() {
+        let mut rng = XorShift64::new(29);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(30);
+            let m = rng.gen_index(4 * n);
+            let edges = random_edges(&mut rng, n, m);
+            let csr = Csr::from_edges(n, &edges);
+            let mut dag = Dag::new(n);
+            for &(from, to, cost) in &edges {
+                dag.add_edge(from, to, cost);
+            }
+            for v in 0..n {
+                assert_eq!(
+                    csr.neighbors(v).to_vec(),
+                    dag.neighbors(v).collect::<Vec<_>>()
+                );
+            }
+            let s = rng.gen_index(n);
+            assert_eq!(csr.dijkstra_all(s), dag.dijkstra_all(s));
+            assert_eq!(csr.bfs(s), dag.bfs(s));
+            assert_eq!(adjacency::bfs(&dag, s), dag.bfs(s));
+        }
+    }
+
+    #[test]
+    fn large() {
+        let (n, m) = (100_000, 200_000);
+        let mut rng = XorShift64::new(30);
+        let mut edges = random_edges(&mut rng, n, m);
+        // a path through every vertex keeps them all reachable
+        edges.extend((1..n).map(|v| (v - 1, v, 1_000)));
+        let g = Csr::from_edges(n, &edges);
+        assert_eq!(g.edge_count(), m + n - 1);
+
+        let dist = g.dijkstra_all(0);
+        assert!(dist.iter().all(Option::is_some));
+        assert!(dist[n - 1].unwrap() <= 1_000 * (n as i64 - 1));
+        assert_eq!(g.bfs(0).iter().flatten().count(), n);
+    }
+}
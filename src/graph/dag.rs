@@ -1,48 +1,364 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::fmt::{self, Write};
 use std::ops::Index;
 
-const INF: i64 = i64::MAX;
+use super::adjacency::{self, Adjacency};
+use super::cost::{Cost, Weight};
+use super::mst::minimum_spanning_tree;
+use super::scc::{self, Scc};
+use crate::util::debug::format_grid;
 
 /// Directed asyclic graph
-pub struct Dag {
-    edges: Vec<Vec<Edge>>,
+///
+/// Edge costs are `i64` unless another [`Weight`] is given, as in `Dag::<u32>::with_size(n)`.
+/// Algorithms relying on [`Cost`] are only available for `i64`.
+pub struct Dag<W = i64> {
+    // every edge ever added, indexed by its id
+    edges: Vec<Edge<W>>,
+    // ids of the edges leaving each vertex, some of them may be removed
+    adj: Vec<Vec<usize>>,
+    // number of removed ids left in adj[v], dropped once they outnumber the live ones
+    stale: Vec<usize>,
+    removed: Vec<bool>,
+    removed_count: usize,
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Edge {
+struct Edge<W> {
     from: usize,
     to: usize,
-    cost: i64,
+    cost: W,
 }
 
+/// Identifier of an edge of a [`Dag`], given by [`Dag::add_edge`].
+///
+/// Ids are `0, 1, 2, ..` in the order edges are added and are never reused after removals,
+/// so data per edge can live in a `Vec` indexed by `id.0`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct EdgeId(pub usize);
+
 impl Dag {
     pub fn new(size: usize) -> Self {
+        Self::with_size(size)
+    }
+}
+
+impl<W: Copy> Dag<W> {
+    pub fn with_size(size: usize) -> Self {
         Self {
-            edges: vec![vec![]; size],
+            edges: vec![],
+            adj: vec![vec![]; size],
+            stale: vec![0; size],
+            removed: vec![],
+            removed_count: 0,
+        }
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: W) -> EdgeId {
+        let id = self.edges.len();
+        self.edges.push(Edge { from, to, cost });
+        self.adj[from].push(id);
+        self.removed.push(false);
+        EdgeId(id)
+    }
+
+    /// Remove exactly one edge from `from` to `to`, the first added of the remaining ones.
+    ///
+    /// Returns `false` if there is no such edge. Use [`Dag::remove_edge_by_id`] to choose among parallel edges.
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        let Some(id) = self.edge_ids(from).find(|id| self.edges[id.0].to == to) else {
+            return false;
+        };
+        self.remove_edge_by_id(id)
+    }
+
+    /// Remove the edge `id` in amortized `O(1)`, `false` if it was already removed.
+    ///
+    /// The edge is only marked removed, so other edges keep their ids and order.
+    /// Marked ids are dropped from the adjacency list of `from` once they are more than
+    /// the remaining ones, so scanning the edges of a vertex stays linear in its live degree.
+    pub fn remove_edge_by_id(&mut self, id: EdgeId) -> bool {
+        if self.removed[id.0] {
+            return false;
         }
+        self.removed[id.0] = true;
+        self.removed_count += 1;
+
+        let v = self.edges[id.0].from;
+        self.stale[v] += 1;
+        if self.stale[v] * 2 > self.adj[v].len() {
+            let removed = &self.removed;
+            self.adj[v].retain(|&id| !removed[id]);
+            self.stale[v] = 0;
+        }
+        true
+    }
+
+    /// Whether the edge `id` has been removed, its [`Dag::edge`] is still available.
+    pub fn is_removed(&self, id: EdgeId) -> bool {
+        self.removed[id.0]
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len() - self.removed_count
+    }
+
+    /// `(from, to, cost)` of the edge `id`, also after it is removed.
+    pub fn edge(&self, id: EdgeId) -> (usize, usize, W) {
+        let e = &self.edges[id.0];
+        (e.from, e.to, e.cost)
+    }
+
+    /// `(to, cost)` of the edges leaving `v`.
+    pub fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.out_edges(v).map(|e| (e.to, e.cost))
+    }
+
+    /// Ids of the edges leaving `v` in the order they were added.
+    pub fn edge_ids(&self, v: usize) -> impl Iterator<Item = EdgeId> + '_ {
+        self.live_ids(v).map(EdgeId)
+    }
+
+    /// `(from, to, cost)` of every edge, grouped by `from` in ascending order.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        self.all_edges().map(|e| (e.from, e.to, e.cost))
+    }
+
+    fn all_edges(&self) -> impl Iterator<Item = Edge<W>> + '_ {
+        (0..self.len())
+            .flat_map(move |v| self.out_edges(v))
+            .cloned()
     }
 
-    pub fn add_edge(&mut self, from: usize, to: usize, cost: i64) {
-        let edge = Edge { from, to, cost };
-        self.edges[from].push(edge);
+    fn out_edges(&self, v: usize) -> impl DoubleEndedIterator<Item = &Edge<W>> + '_ {
+        self.live_ids(v).map(move |id| &self.edges[id])
     }
 
-    pub fn remove_edge(&mut self, from: usize, to: usize) {
-        let adj = &mut self.edges[from];
-        let Some(pos) = adj.iter().position(|edge| edge.to == to) else { return };
-        adj.swap_remove(pos);
+    fn live_ids(&self, v: usize) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.adj[v]
+            .iter()
+            .copied()
+            .filter(move |&id| !self.removed[id])
+    }
+}
+
+impl<W: Copy> Adjacency for Dag<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn for_each_neighbor<F: FnMut(usize, W)>(&self, v: usize, mut f: F) {
+        self.out_edges(v).for_each(|e| f(e.to, e.cost));
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for Dag<W> {
+    /// `Dag {from: [(to, cost), ..], ..}`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dag ")?;
+        f.debug_map()
+            .entries(self.adj.iter().enumerate().map(|(v, adj)| {
+                let adj = adj
+                    .iter()
+                    .filter(|&&id| !self.removed[id])
+                    .map(|&id| (self.edges[id].to, &self.edges[id].cost));
+                (v, adj.collect::<Vec<_>>())
+            }))
+            .finish()
+    }
+}
+
+/// Options of [`Dag::to_dot`].
+#[derive(Default)]
+pub struct DotOptions<'a> {
+    vertex_label: Option<Box<dyn Fn(usize) -> String + 'a>>,
+    highlight_vertices: Vec<usize>,
+    highlight_path: Vec<usize>,
+}
+
+impl<'a> DotOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Label vertices with `f(v)` instead of the vertex index.
+    pub fn vertex_label<F: Fn(usize) -> String + 'a>(mut self, f: F) -> Self {
+        self.vertex_label = Some(Box::new(f));
+        self
     }
 
-    fn size(&self) -> usize {
-        self.edges.len()
+    pub fn highlight_vertices(mut self, vertices: &[usize]) -> Self {
+        self.highlight_vertices = vertices.to_vec();
+        self
     }
 
-    fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
-        self.edges.iter().flatten().cloned()
+    /// Highlight the vertices of `path` and the edges between consecutive ones.
+    pub fn highlight_path(mut self, path: &[usize]) -> Self {
+        self.highlight_path = path.to_vec();
+        self
     }
 }
 
+const DOT_HIGHLIGHT: &str = "red";
+
+impl<W: Copy + fmt::Display> Dag<W> {
+    /// Graphviz DOT representation with edge costs as labels, for debugging.
+    pub fn to_dot(&self, opts: &DotOptions) -> String {
+        let highlighted =
+            |v: usize| opts.highlight_vertices.contains(&v) || opts.highlight_path.contains(&v);
+        let on_path = |from: usize, to: usize| {
+            opts.highlight_path
+                .windows(2)
+                .any(|w| w[0] == from && w[1] == to)
+        };
+
+        let mut dot = String::from("digraph {\n");
+        for v in 0..self.len() {
+            let mut attrs = vec![];
+            if let Some(label) = &opts.vertex_label {
+                attrs.push(format!("label={:?}", label(v)));
+            }
+            if highlighted(v) {
+                attrs.push(format!("color={}", DOT_HIGHLIGHT));
+            }
+            if attrs.is_empty() {
+                writeln!(dot, "    {};", v).unwrap();
+            } else {
+                writeln!(dot, "    {} [{}];", v, attrs.join(", ")).unwrap();
+            }
+        }
+        for e in self.all_edges() {
+            write!(dot, "    {} -> {} [label=\"{}\"", e.from, e.to, e.cost).unwrap();
+            if on_path(e.from, e.to) {
+                write!(dot, ", color={}, penwidth=2", DOT_HIGHLIGHT).unwrap();
+            }
+            dot.push_str("];\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// All pairs shortest path distances.
+///
+/// Raw distances are `i64::MAX` for unreachable pairs
+/// and `i64::MIN` for pairs whose path can go around a negative cycle.
 pub struct ShortestPaths(Vec<Vec<i64>>);
 
+impl ShortestPaths {
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Distance from `i` to `j`, `None` if unreachable or unbounded.
+    pub fn get(&self, i: usize, j: usize) -> Option<i64> {
+        match self.0[i][j] {
+            i64::MIN => None,
+            d => Cost::from(d).finite(),
+        }
+    }
+
+    pub fn is_reachable(&self, i: usize, j: usize) -> bool {
+        self.0[i][j] != i64::MAX
+    }
+
+    /// Whether a walk from `i` to `j` can pass a negative cycle, making the distance negative infinity.
+    pub fn is_unbounded(&self, i: usize, j: usize) -> bool {
+        self.0[i][j] == i64::MIN
+    }
+
+    /// Whether the graph has a negative cycle.
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.len()).any(|i| self.is_unbounded(i, i))
+    }
+
+    pub fn into_inner(self) -> Vec<Vec<i64>> {
+        self.0
+    }
+
+    /// Raw distances from `i`, same as `sp[i]`.
+    pub fn row(&self, i: usize) -> &[i64] {
+        &self.0[i]
+    }
+
+    /// Largest finite distance, the diameter of the graph.
+    pub fn max_finite(&self) -> Option<i64> {
+        self.iter().filter_map(|(_, _, d)| d).max()
+    }
+
+    /// Sum of all finite distances.
+    pub fn sum_finite(&self) -> i128 {
+        self.iter().filter_map(|(_, _, d)| d).map(i128::from).sum()
+    }
+
+    /// Number of ordered pairs `(i, j)` with `i != j` and `j` reachable from `i`.
+    pub fn count_reachable_pairs(&self) -> usize {
+        self.iter()
+            .filter(|&(i, j, _)| i != j && self.is_reachable(i, j))
+            .count()
+    }
+
+    /// `(i, j, distance)` for pairs with a finite distance in row major order.
+    pub fn iter_finite(&self) -> impl Iterator<Item = (usize, usize, i64)> + '_ {
+        self.iter().filter_map(|(i, j, d)| Some((i, j, d?)))
+    }
+
+    /// `(i, j, distance)` for every pair in row major order, distances as in [`ShortestPaths::get`].
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, Option<i64>)> + '_ {
+        let n = self.len();
+        (0..n).flat_map(move |i| (0..n).map(move |j| (i, j, self.get(i, j))))
+    }
+}
+
+/// Matrix with `-` for unreachable and `-inf` for unbounded pairs.
+///
+/// ```text
+/// ShortestPaths
+/// 0 2 6 -
+/// 5 0 4 -
+/// - - 0 -
+/// - - - 0
+/// ```
+impl fmt::Debug for ShortestPaths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells = self
+            .0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&d| match d {
+                        i64::MAX => "-".to_string(),
+                        i64::MIN => "-inf".to_string(),
+                        d => d.to_string(),
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+        writeln!(f, "ShortestPaths")?;
+        write!(f, "{}", format_grid(&cells))
+    }
+}
+
+/// Raw row of distances, unreachable pairs hold the `i64::MAX` sentinel
+/// and unbounded ones `i64::MIN`.
+/// Prefer [`ShortestPaths::get`] before doing arithmetic on a distance.
 impl Index<usize> for ShortestPaths {
     type Output = [i64];
 
@@ -51,149 +367,1177 @@ impl Index<usize> for ShortestPaths {
     }
 }
 
+/// Raw distance, `i64::MAX` if unreachable and `i64::MIN` if unbounded.
+impl Index<(usize, usize)> for ShortestPaths {
+    type Output = i64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.0[i][j]
+    }
+}
+
 impl From<Vec<Vec<i64>>> for ShortestPaths {
     fn from(v: Vec<Vec<i64>>) -> Self {
         ShortestPaths(v)
     }
 }
 
+// Floyd-Warshall stays on `i64` unlike the single source searches: `ShortestPaths` hands out
+// the raw matrix (`sp[i][j]`, `row`, `into_inner`) with `i64::MAX` and `i64::MIN` as the
+// unreachable and unbounded sentinels, and an unsigned `Weight` has no value left for either.
 impl Dag {
+    /// All pairs shortest paths in `O(V^3)`, negative edges are allowed.
+    ///
+    /// Pairs connected through a negative cycle are marked unbounded,
+    /// see [`ShortestPaths::has_negative_cycle`].
+    /// Only for `i64` costs, run [`Dag::dijkstra_all`] from every vertex for other weights.
     pub fn floyd_warshall(&self) -> ShortestPaths {
-        let n = self.size();
-        let mut dp = vec![vec![INF; n]; n];
+        self.floyd_warshall_with(None)
+    }
+
+    /// [`Dag::floyd_warshall`] also recording the next vertex of each shortest path.
+    pub fn floyd_warshall_with_path(&self) -> (ShortestPaths, NextMatrix) {
+        let n = self.len();
+        let mut next = (0..n).map(|_| vec![usize::MAX; n]).collect::<Vec<_>>();
+        let sp = self.floyd_warshall_with(Some(&mut next));
+        // no shortest path to follow around a negative cycle
+        for (i, row) in next.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                if sp.is_unbounded(i, j) {
+                    *v = usize::MAX;
+                }
+            }
+        }
+        (sp, NextMatrix(next))
+    }
+
+    /// `next[i][j]` becomes the vertex after `i` on a shortest path to `j` when given.
+    fn floyd_warshall_with(&self, mut next: Option<&mut Vec<Vec<usize>>>) -> ShortestPaths {
+        let n = self.len();
+        let mut dp = vec![vec![Cost::Infinite; n]; n];
 
         // init self edge to zero
         for (i, adj) in dp.iter_mut().enumerate() {
-            adj[i] = 0
+            adj[i] = Cost::Finite(0)
+        }
+        if let Some(next) = next.as_mut() {
+            (0..n).for_each(|i| next[i][i] = i);
         }
 
-        // write eges
-        self.edges().for_each(|e| {
-            dp[e.from][e.to] = e.cost;
-        });
-
-        let chmin = |a: &mut i64, b: i64| {
-            if b < *a {
-                *a = b
+        // write eges, a self loop or parallel edge must not overwrite a cheaper one
+        self.all_edges().for_each(|e| {
+            if dp[e.from][e.to].chmin(Cost::Finite(e.cost)) {
+                if let Some(next) = next.as_mut() {
+                    next[e.from][e.to] = e.to;
+                }
             }
-        };
+        });
 
         for k in 0..n {
             for i in 0..n {
                 for j in 0..n {
-                    let cost = dp[i][k].saturating_add(dp[k][j]);
-                    chmin(&mut dp[i][j], cost);
+                    let cost = dp[i][k] + dp[k][j];
+                    if dp[i][j].chmin(cost) {
+                        if let Some(next) = next.as_mut() {
+                            next[i][j] = next[i][k];
+                        }
+                    }
+                }
+            }
+        }
+
+        // k lies on a negative cycle iff dp[k][k] < 0
+        let mut unbounded = vec![vec![false; n]; n];
+        for k in (0..n).filter(|&k| dp[k][k] < Cost::Finite(0)) {
+            for i in (0..n).filter(|&i| dp[i][k].is_finite()) {
+                for j in (0..n).filter(|&j| dp[k][j].is_finite()) {
+                    unbounded[i][j] = true;
                 }
             }
         }
 
-        dp.into()
+        dp.into_iter()
+            .zip(unbounded)
+            .map(|(row, unbounded)| {
+                row.into_iter()
+                    .zip(unbounded)
+                    .map(|(d, u)| if u { i64::MIN } else { i64::from(d) })
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>()
+            .into()
     }
 }
 
-impl Dag {
-    //
-    pub fn djkstra(&self, from: usize, to: usize) -> Option<i64> {
-        //  https://doc.rust-lang.org/std/collections/binary_heap/index.html#examples
-        use std::{cmp::Ordering, collections::BinaryHeap};
+/// Next vertex of every shortest path from [`Dag::floyd_warshall_with_path`].
+pub struct NextMatrix(Vec<Vec<usize>>);
+
+impl NextMatrix {
+    /// Vertex after `i` on a shortest path from `i` to `j`, `None` if there is no shortest path.
+    pub fn next(&self, i: usize, j: usize) -> Option<usize> {
+        Some(self.0[i][j]).filter(|&v| v != usize::MAX)
+    }
+
+    /// Vertices of a shortest path from `i` to `j`, both ends included.
+    ///
+    /// `path(i, i)` is `[i]`, `None` if `j` is unreachable or the distance is unbounded.
+    pub fn path(&self, i: usize, j: usize) -> Option<Vec<usize>> {
+        self.next(i, j)?;
+        let mut path = vec![i];
+        let mut v = i;
+        while v != j {
+            v = self.next(v, j)?;
+            path.push(v);
+        }
+        Some(path)
+    }
+}
+
+impl<W: Weight> Dag<W> {
+    /// Shortest distance from `from` to `to`, `None` if unreachable.
+    ///
+    /// Edge costs must not be negative, see [`Dag::try_dijkstra`].
+    pub fn dijkstra(&self, from: usize, to: usize) -> Option<W> {
+        adjacency::dijkstra(self, &[(from, W::ZERO)], Some(to), |_| W::ZERO, &mut 0)[to]
+    }
 
-        #[derive(Copy, Clone, Eq, PartialEq)]
-        struct State {
-            position: usize,
-            cost: i64,
+    /// [`Dag::dijkstra`] that first scans every edge and fails on a negative one,
+    /// for which Dijkstra would silently give a wrong distance.
+    pub fn try_dijkstra(&self, from: usize, to: usize) -> Result<Option<W>, NegativeEdge<W>> {
+        if let Some(e) = self.all_edges().find(|e| e.cost < W::ZERO) {
+            return Err(NegativeEdge {
+                from: e.from,
+                to: e.to,
+                cost: e.cost,
+            });
         }
+        Ok(self.dijkstra(from, to))
+    }
+
+    #[deprecated(note = "use `dijkstra`")]
+    pub fn djkstra(&self, from: usize, to: usize) -> Option<W> {
+        self.dijkstra(from, to)
+    }
+
+    /// Shortest distances from `from` to every vertex, `None` for unreachable ones.
+    pub fn dijkstra_all(&self, from: usize) -> Vec<Option<W>> {
+        adjacency::dijkstra_all(self, from)
+    }
+
+    /// Shortest distances when starting at each `(vertex, initial cost)` of `sources` at once,
+    /// the minimum over the sources without adding a super source.
+    pub fn dijkstra_multi(&self, sources: &[(usize, W)]) -> Vec<Option<W>> {
+        adjacency::dijkstra(self, sources, None, |_| W::ZERO, &mut 0)
+    }
+}
+
+impl Dag {
+    /// Shortest distances from `from` when every edge costs 0 or 1, `None` if unreachable.
+    ///
+    /// `O(V + E)` with a deque in place of Dijkstra's heap.
+    pub fn bfs01(&self, from: usize) -> Vec<Option<i64>> {
+        adjacency::bfs01(self, from)
+    }
+}
+
+impl<W: Copy> Dag<W> {
+    /// Number of edges on a shortest path from `from` to each vertex ignoring costs, `None` if unreachable.
+    pub fn bfs(&self, from: usize) -> Vec<Option<u32>> {
+        self.bfs_parents(from).0
+    }
+
+    /// Vertices of a path from `from` to `to` with the fewest edges, both ends included.
+    pub fn bfs_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let (dist, parent) = self.bfs_parents(from);
+        adjacency::path_to(&dist, &parent, to)
+    }
+
+    /// Distance of each vertex from the nearest of `sources` ignoring costs, `None` if unreachable.
+    pub fn bfs_multi(&self, sources: &[usize]) -> Vec<Option<u32>> {
+        adjacency::bfs_parents(self, sources).0
+    }
+
+    fn bfs_parents(&self, from: usize) -> (Vec<Option<u32>>, Vec<usize>) {
+        adjacency::bfs_parents(self, &[from])
+    }
+}
+
+/// Error of [`Dag::try_dijkstra`], the first negative edge found.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NegativeEdge<W = i64> {
+    pub from: usize,
+    pub to: usize,
+    pub cost: W,
+}
+
+impl<W: fmt::Debug> fmt::Display for NegativeEdge<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "negative edge {} -> {} of cost {:?}",
+            self.from, self.to, self.cost
+        )
+    }
+}
+
+impl<W: fmt::Debug> std::error::Error for NegativeEdge<W> {}
+
+/// Error of [`Dag::bellman_ford`] and [`Dag::johnson`], some negative cycle is reachable from the source.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NegativeCycle {
+    /// Distances as in the `Ok` case, `None` also for unbounded vertices.
+    pub dist: Vec<Option<i64>>,
+    /// `true` for vertices whose distance is negative infinity.
+    pub unbounded: Vec<bool>,
+}
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.unbounded.iter().filter(|&&b| b).count();
+        write!(f, "negative cycle makes {} vertices unbounded", count)
+    }
+}
+
+impl std::error::Error for NegativeCycle {}
+
+impl Dag {
+    /// Single source shortest distances allowing negative edges, `None` if unreachable.
+    ///
+    /// O(VE). Fails only if a negative cycle is reachable from `from`,
+    /// the error still carries the distances of the vertices it does not affect.
+    /// A distance falling below `i64::MIN` is reported as unbounded as well.
+    pub fn bellman_ford(&self, from: usize) -> Result<Vec<Option<i64>>, NegativeCycle> {
+        let n = self.len();
+        let mut dist = vec![Cost::Infinite; n];
+        dist[from] = Cost::Finite(0);
+        // vertices whose distance overflowed below i64::MIN, which `Cost` would saturate
+        // and so stop improving, hiding a negative cycle from the check below
+        let mut overflowed = vec![];
+
+        let mut relax = |dist: &mut [Cost]| {
+            let mut updated = vec![];
+            for e in self.all_edges() {
+                let Cost::Finite(d) = dist[e.from] else {
+                    continue;
+                };
+                match d.checked_add(e.cost) {
+                    Some(cost) => {
+                        if dist[e.to].chmin(Cost::Finite(cost)) {
+                            updated.push(e.to);
+                        }
+                    }
+                    None if e.cost < 0 => overflowed.push(e.to),
+                    None => (),
+                }
+            }
+            updated
+        };
 
-        // BinaryHeap::pop require item to impl Ord
-        impl Ord for State {
-            fn cmp(&self, other: &Self) -> Ordering {
-                // I'm not sure why we should compare position
-                other
-                    .cost
-                    .cmp(&self.cost)
-                    .then_with(|| self.position.cmp(&other.position))
+        for _ in 1..n {
+            if relax(&mut dist).is_empty() {
+                break;
             }
         }
 
-        impl PartialOrd for State {
-            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-                Some(self.cmp(other))
+        // anything still improving lies on or after a negative cycle
+        let mut stack = relax(&mut dist);
+        stack.append(&mut overflowed);
+        let mut unbounded = vec![false; n];
+        while let Some(v) = stack.pop() {
+            if !unbounded[v] {
+                unbounded[v] = true;
+                stack.extend(self.out_edges(v).map(|e| e.to));
             }
         }
 
-        let mut dist = (0..self.size()).map(|_| INF).collect::<Vec<_>>();
-        dist[from] = 0;
+        let dist = dist
+            .iter()
+            .zip(&unbounded)
+            .map(|(d, &u)| if u { None } else { d.finite() })
+            .collect();
+        if unbounded.contains(&true) {
+            Err(NegativeCycle { dist, unbounded })
+        } else {
+            Ok(dist)
+        }
+    }
+}
 
-        let mut queue = BinaryHeap::new();
-        queue.push(State {
-            position: from,
-            cost: 0,
-        });
+impl Dag {
+    /// All pairs shortest paths by Johnson's algorithm in `O(VE log V)`, negative edges are allowed.
+    ///
+    /// Bellman-Ford from a virtual source with a 0 edge to every vertex gives potentials `p`,
+    /// which make every edge cost `cost + p[from] - p[to]` non-negative for Dijkstra from each vertex.
+    /// Fails if there is any negative cycle, the error is then as seen from the virtual source.
+    pub fn johnson(&self) -> Result<ShortestPaths, NegativeCycle> {
+        let n = self.len();
+        let mut g = Dag::new(n + 1);
+        for e in self.all_edges() {
+            g.add_edge(e.from, e.to, e.cost);
+        }
+        for v in 0..n {
+            g.add_edge(n, v, 0);
+        }
+        let potential = match g.bellman_ford(n) {
+            Ok(dist) => dist.into_iter().map(Option::unwrap).collect::<Vec<_>>(),
+            Err(mut e) => {
+                e.dist.truncate(n);
+                e.unbounded.truncate(n);
+                return Err(e);
+            }
+        };
+
+        let mut reweighted = Dag::new(n);
+        for e in self.all_edges() {
+            reweighted.add_edge(e.from, e.to, e.cost + potential[e.from] - potential[e.to]);
+        }
+        let dist = (0..n)
+            .map(|i| {
+                reweighted
+                    .dijkstra_all(i)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(j, d)| d.map_or(i64::MAX, |d| d - potential[i] + potential[j]))
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+        Ok(dist.into())
+    }
+}
+
+impl Dag {
+    /// Maximum cost over all paths, including the empty ones.
+    ///
+    /// # Panics
+    /// If the graph has a cycle.
+    pub fn longest_path(&self) -> i64 {
+        let mut dp = vec![0; self.len()];
+        for v in self.topological_order() {
+            for e in self.out_edges(v) {
+                dp[e.to] = dp[e.to].max(dp[v] + e.cost);
+            }
+        }
+        dp.into_iter().max().unwrap_or(0)
+    }
 
-        while let Some(State { position, cost }) = queue.pop() {
-            if position == to {
-                return Some(cost);
+    /// Maximum cost of a path from `from` to each vertex, `None` if unreachable.
+    ///
+    /// # Panics
+    /// If the graph has a cycle.
+    pub fn longest_path_from(&self, from: usize) -> Vec<Option<i64>> {
+        let mut dp = vec![None; self.len()];
+        dp[from] = Some(0);
+        for v in self.topological_order() {
+            let Some(d) = dp[v] else { continue };
+            for e in self.out_edges(v) {
+                dp[e.to] = dp[e.to].max(Some(d + e.cost));
             }
+        }
+        dp
+    }
+}
+
+impl Dag {
+    /// Shortest distance from `from` to `to` by A* search, `None` if unreachable.
+    ///
+    /// `h(v)` estimates the distance from `v` to `to`. It must be consistent,
+    /// `h(v) <= cost + h(u)` for every edge `v -> u` and `h(to) == 0`, which is checked in debug builds.
+    /// Vertices are popped in order of `distance + h`, so a good estimate visits fewer of them
+    /// than [`dijkstra`](Self::dijkstra), and `|_| 0` is exactly Dijkstra.
+    pub fn astar<H: Fn(usize) -> i64>(&self, from: usize, to: usize, h: H) -> Option<i64> {
+        self.astar_counted(from, to, h).0
+    }
+
+    /// A* along with the number of vertices popped and expanded.
+    fn astar_counted<H: Fn(usize) -> i64>(
+        &self,
+        from: usize,
+        to: usize,
+        h: H,
+    ) -> (Option<i64>, usize) {
+        let mut popped = 0;
+        let dist = adjacency::dijkstra(self, &[(from, 0)], Some(to), h, &mut popped);
+        (dist[to], popped)
+    }
+}
+
+impl Dag {
+    /// Shortest path from `from` to `to` as `(cost, vertices)`, both ends included.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(i64, Vec<usize>)> {
+        let banned = vec![false; self.len()];
+        let (cost, path) = self.shortest_path_avoiding(from, to, &banned, &HashSet::new())?;
+        Some((cost.finite()?, path))
+    }
+
+    /// Shortest path from `from` to `to` as `(cost, edges)`, which tells parallel edges apart.
+    pub fn shortest_path_edges(&self, from: usize, to: usize) -> Option<(i64, Vec<EdgeId>)> {
+        let banned = vec![false; self.len()];
+        let (cost, edges) = self.shortest_edges_avoiding(from, to, &banned, &HashSet::new())?;
+        Some((cost.finite()?, edges.into_iter().map(EdgeId).collect()))
+    }
+
+    /// Minimum spanning tree with edge directions ignored, as `(total cost, ids of its edges)`.
+    ///
+    /// `None` if the graph is disconnected. Removed edges are skipped,
+    /// and ties are broken by the smaller id.
+    pub fn minimum_spanning_tree_edges(&self) -> Option<(i64, Vec<EdgeId>)> {
+        let ids = (0..self.edges.len())
+            .filter(|&id| !self.removed[id])
+            .collect::<Vec<_>>();
+        let list = ids
+            .iter()
+            .map(|&id| self.edge(EdgeId(id)))
+            .collect::<Vec<_>>();
+        let (total, chosen) = minimum_spanning_tree(self.len(), &list)?;
+        Some((total, chosen.into_iter().map(|i| EdgeId(ids[i])).collect()))
+    }
+
+    /// Dijkstra that never enters `banned_vertices` nor uses an edge `(from, to)` in `banned_edges`.
+    fn shortest_path_avoiding(
+        &self,
+        from: usize,
+        to: usize,
+        banned_vertices: &[bool],
+        banned_edges: &HashSet<(usize, usize)>,
+    ) -> Option<(Cost, Vec<usize>)> {
+        let (cost, edges) =
+            self.shortest_edges_avoiding(from, to, banned_vertices, banned_edges)?;
+        let path = std::iter::once(from)
+            .chain(edges.into_iter().map(|id| self.edges[id].to))
+            .collect();
+        Some((cost, path))
+    }
+
+    /// [`Dag::shortest_path_avoiding`] as the ids of the edges on the path.
+    fn shortest_edges_avoiding(
+        &self,
+        from: usize,
+        to: usize,
+        banned_vertices: &[bool],
+        banned_edges: &HashSet<(usize, usize)>,
+    ) -> Option<(Cost, Vec<usize>)> {
+        let mut dist = vec![Cost::Infinite; self.len()];
+        // id of the last edge of the shortest path to each vertex
+        let mut prev = vec![usize::MAX; self.len()];
+        dist[from] = Cost::Finite(0);
+        let mut queue = BinaryHeap::from([Reverse((Cost::Finite(0), from))]);
 
-            if cost > dist[position] {
+        while let Some(Reverse((cost, v))) = queue.pop() {
+            if v == to {
+                break;
+            }
+            if cost > dist[v] {
                 continue;
             }
+            for id in self.live_ids(v) {
+                let edge = &self.edges[id];
+                if banned_vertices[edge.to] || banned_edges.contains(&(v, edge.to)) {
+                    continue;
+                }
+                let next = cost + edge.cost;
+                if dist[edge.to].chmin(next) {
+                    prev[edge.to] = id;
+                    queue.push(Reverse((next, edge.to)));
+                }
+            }
+        }
+
+        if !dist[to].is_finite() {
+            return None;
+        }
+        let mut path = vec![];
+        let mut v = to;
+        while v != from {
+            path.push(prev[v]);
+            v = self.edges[prev[v]].from;
+        }
+        path.reverse();
+        Some((dist[to], path))
+    }
+
+    /// Cost of walking `path`, taking the cheapest of parallel edges.
+    fn path_cost(&self, path: &[usize]) -> Cost {
+        path.windows(2)
+            .map(|w| {
+                self.out_edges(w[0])
+                    .filter(|e| e.to == w[1])
+                    .map(|e| Cost::Finite(e.cost))
+                    .min()
+                    .unwrap_or(Cost::Infinite)
+            })
+            .fold(Cost::Finite(0), |acc, c| acc + c)
+    }
+
+    /// Up to `k` shortest loopless paths from `from` to `to` in increasing cost (Yen's algorithm).
+    ///
+    /// Paths are distinct as vertex sequences, so parallel edges yield a path once.
+    pub fn k_shortest_paths(&self, from: usize, to: usize, k: usize) -> Vec<(i64, Vec<usize>)> {
+        let mut found: Vec<(Cost, Vec<usize>)> = vec![];
+        let mut candidates = BinaryHeap::new();
+        let mut seen = HashSet::new();
+        if let Some(first) =
+            self.shortest_path_avoiding(from, to, &vec![false; self.len()], &HashSet::new())
+        {
+            seen.insert(first.1.clone());
+            candidates.push(Reverse(first));
+        }
+
+        while found.len() < k {
+            let Some(Reverse(best)) = candidates.pop() else { break };
+            found.push(best);
+            let last = &found[found.len() - 1].1;
 
-            for edge in &self.edges[position] {
-                let next = State {
-                    position: edge.to,
-                    cost: edge.cost + cost,
+            for i in 0..last.len() - 1 {
+                let (root, spur) = (&last[..=i], last[i]);
+                let mut banned_vertices = vec![false; self.len()];
+                for &v in &root[..i] {
+                    banned_vertices[v] = true;
+                }
+                let banned_edges = found
+                    .iter()
+                    .filter(|(_, p)| p.len() > i + 1 && &p[..=i] == root)
+                    .map(|(_, p)| (p[i], p[i + 1]))
+                    .collect::<HashSet<_>>();
+
+                let Some((spur_cost, spur_path)) =
+                    self.shortest_path_avoiding(spur, to, &banned_vertices, &banned_edges)
+                else {
+                    continue;
                 };
-                if next.cost < dist[next.position] {
-                    queue.push(next);
-                    dist[next.position] = next.cost;
+                let cost = self.path_cost(root) + spur_cost;
+                let path = root[..i]
+                    .iter()
+                    .chain(&spur_path)
+                    .copied()
+                    .collect::<Vec<_>>();
+                if cost.is_finite() && seen.insert(path.clone()) {
+                    candidates.push(Reverse((cost, path)));
                 }
             }
         }
 
-        None
+        found
+            .into_iter()
+            .map(|(cost, path)| (cost.finite().unwrap(), path))
+            .collect()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<W: Copy> Dag<W> {
+    /// Kahn's algorithm, `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut indegree = self.indegrees();
+        let mut stack = (0..self.len())
+            .filter(|&v| indegree[v] == 0)
+            .collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(self.len());
 
-    #[test]
-    fn floyd_warshall() {
-        let mut g = Dag::new(4);
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for e in self.out_edges(v) {
+                indegree[e.to] -= 1;
+                if indegree[e.to] == 0 {
+                    stack.push(e.to);
+                }
+            }
+        }
 
-        g.add_edge(0, 1, 5);
-        g.add_edge(0, 2, 2);
-        g.add_edge(1, 3, 4);
-        g.add_edge(2, 1, 2);
-        g.add_edge(2, 3, 3);
+        (order.len() == self.len()).then_some(order)
+    }
 
-        let sp = g.floyd_warshall();
+    /// Lexicographically smallest topological order, `None` if the graph has a cycle.
+    pub fn topological_sort_lexicographic(&self) -> Option<Vec<usize>> {
+        let mut indegree = self.indegrees();
+        let mut queue = (0..self.len())
+            .filter(|&v| indegree[v] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut order = Vec::with_capacity(self.len());
 
-        assert_eq!(sp[0][3], 5);
-        assert_eq!(sp[0][1], 4);
+        while let Some(Reverse(v)) = queue.pop() {
+            order.push(v);
+            for e in self.out_edges(v) {
+                indegree[e.to] -= 1;
+                if indegree[e.to] == 0 {
+                    queue.push(Reverse(e.to));
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
     }
 
-    #[test]
-    fn djkstra() {
-        // stole from std
-        //
-        //                  7
-        //          +-----------------+
-        //          |                 |
-        //          v   1        2    |  2
-        //          0 -----> 1 -----> 3 ---> 4
-        //          |        ^        ^      ^
-        //          |        | 1      |      |
-        //          |        |        | 3    | 1
-        //          +------> 2 -------+      |
-        //           10      |               |
-        //                   +---------------+
+    pub fn is_acyclic(&self) -> bool {
+        self.find_cycle().is_none()
+    }
 
-        let mut g = Dag::new(5);
+    /// Vertices `[v0, v1, .., vk]` of some directed cycle `v0 -> v1 -> .. -> vk -> v0`.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Color {
+            White,
+            Gray,
+            Black,
+        }
 
-        g.add_edge(0, 1, 1);
-        g.add_edge(0, 2, 10);
+        let mut color = vec![Color::White; self.len()];
+        // (vertex, index of the next edge to visit)
+        let mut stack: Vec<(usize, usize)> = vec![];
+
+        for root in 0..self.len() {
+            if color[root] != Color::White {
+                continue;
+            }
+            color[root] = Color::Gray;
+            stack.push((root, 0));
+
+            while let Some(&mut (v, ref mut i)) = stack.last_mut() {
+                let Some(&id) = self.adj[v].get(*i) else {
+                    color[v] = Color::Black;
+                    stack.pop();
+                    continue;
+                };
+                *i += 1;
+                if self.removed[id] {
+                    continue;
+                }
+                let e = &self.edges[id];
+                match color[e.to] {
+                    Color::White => {
+                        color[e.to] = Color::Gray;
+                        stack.push((e.to, 0));
+                    }
+                    Color::Gray => {
+                        let start = stack.iter().rposition(|&(u, _)| u == e.to).unwrap();
+                        return Some(stack[start..].iter().map(|&(u, _)| u).collect());
+                    }
+                    Color::Black => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Strongly connected components in topological order, see [`Scc`].
+    pub fn scc(&self) -> Scc {
+        let adj = (0..self.len())
+            .map(|v| self.out_edges(v).map(|e| e.to).collect())
+            .collect::<Vec<_>>();
+        scc::tarjan(&adj)
+    }
+
+    /// Number of edges of the longest path, ignoring costs.
+    ///
+    /// # Panics
+    /// If the graph has a cycle.
+    pub fn longest_path_len(&self) -> usize {
+        let mut dp = vec![0; self.len()];
+        for v in self.topological_order() {
+            for e in self.out_edges(v) {
+                dp[e.to] = dp[e.to].max(dp[v] + 1);
+            }
+        }
+        dp.into_iter().max().unwrap_or(0)
+    }
+
+    fn topological_order(&self) -> Vec<usize> {
+        self.topological_sort()
+            .expect("longest path of a graph with a cycle")
+    }
+
+    /// Number of paths from `from` to `to` modulo `modulus`, `None` if infinitely many.
+    pub fn count_paths(&self, from: usize, to: usize, modulus: u64) -> Option<u64> {
+        self.count_paths_from(from, modulus)[to]
+    }
+
+    /// Number of paths from `from` to each vertex modulo `modulus` (`< 2^63`).
+    ///
+    /// `None` for vertices reachable from a cycle reachable from `from`,
+    /// other cycles do not matter. Parallel edges count as distinct paths.
+    pub fn count_paths_from(&self, from: usize, modulus: u64) -> Vec<Option<u64>> {
+        let n = self.len();
+        let mut reachable = vec![false; n];
+        let mut stack = vec![from];
+        reachable[from] = true;
+        while let Some(v) = stack.pop() {
+            for e in self.out_edges(v) {
+                if !reachable[e.to] {
+                    reachable[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+
+        // Kahn's algorithm on the reachable part, leaving vertices behind a cycle unvisited
+        let mut indegree = vec![0; n];
+        self.all_edges()
+            .filter(|e| reachable[e.from])
+            .for_each(|e| indegree[e.to] += 1);
+        let mut count = vec![None; n];
+        (0..n)
+            .filter(|&v| !reachable[v])
+            .for_each(|v| count[v] = Some(0));
+        let mut ways = vec![0; n];
+        ways[from] = 1 % modulus;
+        // an incoming edge of `from` closes a cycle through it
+        let mut stack = if indegree[from] == 0 {
+            vec![from]
+        } else {
+            vec![]
+        };
+        while let Some(v) = stack.pop() {
+            count[v] = Some(ways[v]);
+            for e in self.out_edges(v) {
+                ways[e.to] = (ways[e.to] + ways[v]) % modulus;
+                indegree[e.to] -= 1;
+                if indegree[e.to] == 0 {
+                    stack.push(e.to);
+                }
+            }
+        }
+        count
+    }
+
+    fn indegrees(&self) -> Vec<usize> {
+        let mut indegree = vec![0; self.len()];
+        self.all_edges().for_each(|e| indegree[e.to] += 1);
+        indegree
+    }
+}
+
+impl<W: Copy + Ord> Dag<W> {
+    /// Graph of the strongly connected components and the component of each vertex.
+    ///
+    /// Components are numbered in topological order as in [`Dag::scc`].
+    /// Edges inside a component are dropped and parallel edges between two components
+    /// are merged into one with the minimum cost.
+    pub fn condensation(&self) -> (Dag<W>, Vec<usize>) {
+        let Scc {
+            components,
+            component_id: id,
+        } = self.scc();
+        let mut edges = self
+            .all_edges()
+            .filter(|e| id[e.from] != id[e.to])
+            .map(|e| (id[e.from], id[e.to], e.cost))
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup_by_key(|&mut (from, to, _)| (from, to));
+
+        let mut dag = Dag::with_size(components.len());
+        for (from, to, cost) in edges {
+            dag.add_edge(from, to, cost);
+        }
+        (dag, id)
+    }
+}
+
+impl<W: Copy> Dag<W> {
+    /// Immediate dominator of each vertex, `None` for `root` and vertices unreachable from it.
+    ///
+    /// Lengauer-Tarjan with the simple eval/link, `O(m log n)`.
+    pub fn dominator_tree(&self, root: usize) -> Vec<Option<usize>> {
+        const NONE: usize = usize::MAX;
+        let n = self.len();
+
+        // work on dfs numbers, order[i] is the vertex numbered i
+        let mut num = vec![NONE; n];
+        let mut order = vec![];
+        let mut parent = vec![];
+        let mut stack = vec![(root, NONE)];
+        while let Some((v, p)) = stack.pop() {
+            if num[v] != NONE {
+                continue;
+            }
+            num[v] = order.len();
+            order.push(v);
+            parent.push(p);
+            for edge in self.out_edges(v).rev() {
+                if num[edge.to] == NONE {
+                    stack.push((edge.to, num[v]));
+                }
+            }
+        }
+        let m = order.len();
+        let mut pred = vec![vec![]; m];
+        for edge in self.all_edges() {
+            if num[edge.from] != NONE && num[edge.to] != NONE {
+                pred[num[edge.to]].push(num[edge.from]);
+            }
+        }
+
+        let mut semi = (0..m).collect::<Vec<_>>();
+        let mut label = (0..m).collect::<Vec<_>>();
+        let mut ancestor = vec![NONE; m];
+        let mut idom = vec![NONE; m];
+        let mut bucket = vec![vec![]; m];
+
+        let mut eval = |v: usize, semi: &[usize], ancestor: &mut [usize]| -> usize {
+            if ancestor[v] == NONE {
+                return v;
+            }
+            let mut path = vec![];
+            let mut x = v;
+            while ancestor[ancestor[x]] != NONE {
+                path.push(x);
+                x = ancestor[x];
+            }
+            // compress from the top so each ancestor is already done
+            while let Some(x) = path.pop() {
+                let a = ancestor[x];
+                if semi[label[a]] < semi[label[x]] {
+                    label[x] = label[a];
+                }
+                ancestor[x] = ancestor[a];
+            }
+            label[v]
+        };
+
+        for w in (1..m).rev() {
+            for &v in &pred[w] {
+                let u = eval(v, &semi, &mut ancestor);
+                semi[w] = semi[w].min(semi[u]);
+            }
+            bucket[semi[w]].push(w);
+            ancestor[w] = parent[w];
+            for v in std::mem::take(&mut bucket[parent[w]]) {
+                let u = eval(v, &semi, &mut ancestor);
+                idom[v] = if semi[u] < semi[v] { u } else { parent[w] };
+            }
+        }
+        for w in 1..m {
+            if idom[w] != semi[w] {
+                idom[w] = idom[idom[w]];
+            }
+        }
+
+        let mut result = vec![None; n];
+        for w in 1..m {
+            result[order[w]] = Some(order[idom[w]]);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floyd_warshall() {
+        let mut g = Dag::new(4);
+
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(1, 3, 4);
+        g.add_edge(2, 1, 2);
+        g.add_edge(2, 3, 3);
+
+        let sp = g.floyd_warshall();
+
+        assert_eq!(sp[0][3], 5);
+        assert_eq!(sp[0][1], 4);
+    }
+
+    #[test]
+    fn shortest_paths_unreachable() {
+        // 0 -> 1 -> 2, 3 isolated
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 3);
+        g.add_edge(1, 2, 4);
+
+        let sp = g.floyd_warshall();
+
+        assert_eq!(sp.len(), 4);
+        assert_eq!(sp.get(0, 2), Some(7));
+        assert_eq!(sp.get(2, 0), None);
+        assert_eq!(sp.get(3, 3), Some(0));
+        assert!(sp.is_reachable(0, 1));
+        assert!(!sp.is_reachable(0, 3));
+        assert_eq!(sp[(0, 2)], sp[0][2]);
+        assert_eq!(sp[(2, 0)], i64::MAX);
+
+        let all = sp.iter().collect::<Vec<_>>();
+        assert_eq!(all.len(), 16);
+        assert_eq!(all[2], (0, 2, Some(7)));
+        assert_eq!(all.iter().filter(|(_, _, d)| d.is_some()).count(), 7);
+    }
+
+    #[test]
+    fn shortest_paths_summary() {
+        // 0 <-> 1 -> 2, 3 isolated
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 2);
+        g.add_edge(1, 0, 5);
+        g.add_edge(1, 2, 4);
+
+        let sp = g.floyd_warshall();
+
+        assert_eq!(sp.row(0), &[0, 2, 6, i64::MAX]);
+        assert_eq!(
+            format!("{:?}", sp),
+            "ShortestPaths\n0 2 6 -\n5 0 4 -\n- - 0 -\n- - - 0"
+        );
+        assert_eq!(
+            sp.iter_finite().collect::<Vec<_>>(),
+            vec![
+                (0, 0, 0),
+                (0, 1, 2),
+                (0, 2, 6),
+                (1, 0, 5),
+                (1, 1, 0),
+                (1, 2, 4),
+                (2, 2, 0),
+                (3, 3, 0)
+            ]
+        );
+        assert_eq!(sp.max_finite(), Some(6));
+        assert_eq!(sp.sum_finite(), 2 + 6 + 5 + 4);
+        assert_eq!(sp.count_reachable_pairs(), 4);
+        assert_eq!(sp.into_inner()[1], vec![5, 0, 4, i64::MAX]);
+    }
+
+    #[test]
+    fn floyd_warshall_negative_cycle() {
+        // 1 -> 2 -> 3 -> 1 costs -1, 0 leads into it, 3 -> 4 leaves it, 5 -> 0 stays clear of it
+        let mut g = Dag::new(6);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 1, -3);
+        g.add_edge(3, 4, 2);
+        g.add_edge(5, 0, 4);
+
+        let sp = g.floyd_warshall();
+        assert!(sp.has_negative_cycle());
+        for (i, j) in [(0, 1), (1, 1), (2, 4), (5, 3), (5, 4)] {
+            assert!(sp.is_unbounded(i, j), "{} -> {}", i, j);
+            assert!(sp.is_reachable(i, j));
+            assert_eq!(sp.get(i, j), None);
+            assert_eq!(sp[(i, j)], i64::MIN);
+        }
+        // these avoid the cycle
+        assert_eq!(sp.get(5, 0), Some(4));
+        assert_eq!(sp.get(0, 0), Some(0));
+        assert_eq!(sp.get(4, 4), Some(0));
+        assert!(!sp.is_reachable(4, 0));
+        assert_eq!(sp.count_reachable_pairs(), 4 + 3 + 3 + 3 + 5);
+        assert!(format!("{:?}", sp).contains("\n   4 -inf -inf -inf -inf    0"));
+    }
+
+    #[test]
+    fn floyd_warshall_negative_edges() {
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 4);
+        g.add_edge(0, 2, 2);
+        g.add_edge(2, 1, -3);
+        g.add_edge(1, 3, 2);
+        g.add_edge(3, 2, 1);
+        g.add_edge(0, 0, 3);
+        g.add_edge(0, 1, 5);
+
+        let sp = g.floyd_warshall();
+        assert!(!sp.has_negative_cycle());
+        assert_eq!(sp.get(0, 1), Some(-1));
+        assert_eq!(sp.get(0, 3), Some(1));
+        assert_eq!(sp.get(3, 1), Some(-2));
+        for s in 0..4 {
+            assert_eq!(
+                Ok((0..4).map(|t| sp.get(s, t)).collect()),
+                g.bellman_ford(s)
+            );
+        }
+    }
+
+    #[test]
+    fn johnson_matches_floyd_warshall() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(41);
+        let mut checked = 0;
+        for _ in 0..300 {
+            let n = 1 + rng.gen_index(12);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(3 * n) {
+                let cost = rng.gen_range(0..40) as i64 - 8;
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), cost);
+            }
+            let sp = g.floyd_warshall();
+            match g.johnson() {
+                Ok(johnson) => {
+                    assert!(!sp.has_negative_cycle());
+                    assert_eq!(johnson.into_inner(), sp.into_inner());
+                    checked += 1;
+                }
+                Err(_) => assert!(sp.has_negative_cycle()),
+            }
+        }
+        assert!(checked > 100);
+    }
+
+    #[test]
+    fn johnson_negative_cycle() {
+        // 1 -> 2 -> 1 costs -1, unreachable from 0 which johnson still rejects
+        let mut g = Dag::new(4);
+        g.add_edge(0, 3, 5);
+        g.add_edge(1, 2, 2);
+        g.add_edge(2, 1, -3);
+        let e = g.johnson().unwrap_err();
+        assert_eq!(e.unbounded, vec![false, true, true, false]);
+        assert_eq!(e.dist[0], Some(0));
+        assert_eq!(e.dist[1], None);
+
+        g.remove_edge(2, 1);
+        g.add_edge(2, 1, -2);
+        let sp = g.johnson().unwrap();
+        assert_eq!(sp.get(1, 2), Some(2));
+        assert_eq!(sp.get(2, 1), Some(-2));
+        assert_eq!(sp.get(0, 3), Some(5));
+        assert!(!sp.is_reachable(3, 0));
+    }
+
+    #[test]
+    fn floyd_warshall_with_path() {
+        let mut g = Dag::new(5);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(1, 3, 4);
+        g.add_edge(2, 1, 2);
+        g.add_edge(2, 3, 3);
+
+        let (sp, next) = g.floyd_warshall_with_path();
+        assert_eq!(next.path(0, 1), Some(vec![0, 2, 1]));
+        assert_eq!(next.path(0, 3), Some(vec![0, 2, 3]));
+        assert_eq!(next.path(3, 0), None);
+        assert_eq!(next.path(4, 4), Some(vec![4]));
+        for i in 0..5 {
+            for j in 0..5 {
+                let Some(path) = next.path(i, j) else {
+                    assert!(!sp.is_reachable(i, j));
+                    continue;
+                };
+                assert_eq!(g.path_cost(&path), Cost::Finite(sp[i][j]));
+            }
+        }
+    }
+
+    #[test]
+    fn floyd_warshall_with_path_negative() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(28);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(8);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(3 * n) {
+                let cost = rng.gen_range(0..20) as i64 - 3;
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), cost);
+            }
+            let (sp, next) = g.floyd_warshall_with_path();
+            for i in 0..n {
+                for j in 0..n {
+                    match (sp.get(i, j), next.path(i, j)) {
+                        (Some(d), Some(path)) => assert_eq!(g.path_cost(&path), Cost::Finite(d)),
+                        (None, None) => {}
+                        other => panic!("{} -> {}: {:?}", i, j, other),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(2, 1, 2);
+
+        let names = ["s", "t", "u"];
+        let dot = g.to_dot(
+            &DotOptions::new()
+                .vertex_label(|v| names[v].to_string())
+                .highlight_path(&[0, 2, 1]),
+        );
+
+        assert_eq!(
+            dot,
+            r#"digraph {
+    0 [label="s", color=red];
+    1 [label="t", color=red];
+    2 [label="u", color=red];
+    0 -> 1 [label="5"];
+    0 -> 2 [label="2", color=red, penwidth=2];
+    2 -> 1 [label="2", color=red, penwidth=2];
+}
+"#
+        );
+
+        let dot = g.to_dot(&DotOptions::new().highlight_vertices(&[1]));
+        assert!(dot.contains("    0;\n"));
+        assert!(dot.contains("    1 [color=red];\n"));
+    }
+
+    #[test]
+    fn accessors() {
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 5);
+        g.add_edge(2, 0, -1);
+        g.add_edge(0, 3, 2);
+        g.add_edge(0, 1, 7);
+
+        assert_eq!(g.len(), 4);
+        assert!(!g.is_empty());
+        assert!(Dag::new(0).is_empty());
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(
+            g.neighbors(0).collect::<Vec<_>>(),
+            vec![(1, 5), (3, 2), (1, 7)]
+        );
+        assert_eq!(g.neighbors(1).count(), 0);
+        assert_eq!(
+            g.edges().collect::<Vec<_>>(),
+            vec![(0, 1, 5), (0, 3, 2), (0, 1, 7), (2, 0, -1)]
+        );
+
+        g.remove_edge(0, 1);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.neighbors(0).filter(|&(to, _)| to == 1).count(), 1);
+    }
+
+    #[test]
+    fn debug_dag() {
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, -1);
+
+        assert_eq!(
+            format!("{:?}", g),
+            "Dag {0: [(1, 5), (2, -1)], 1: [], 2: []}"
+        );
+    }
+
+    #[test]
+    fn dijkstra() {
+        // stole from std
+        //
+        //                  7
+        //          +-----------------+
+        //          |                 |
+        //          v   1        2    |  2
+        //          0 -----> 1 -----> 3 ---> 4
+        //          |        ^        ^      ^
+        //          |        | 1      |      |
+        //          |        |        | 3    | 1
+        //          +------> 2 -------+      |
+        //           10      |               |
+        //                   +---------------+
+
+        let mut g = Dag::new(5);
+
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 10);
         g.add_edge(1, 3, 2);
         g.add_edge(2, 1, 1);
         g.add_edge(2, 3, 3);
@@ -201,7 +1545,866 @@ mod tests {
         g.add_edge(3, 0, 7);
         g.add_edge(3, 4, 2);
 
-        assert_eq!(g.djkstra(0, 4), Some(5));
-        assert_eq!(g.djkstra(0, 3), Some(3));
+        assert_eq!(g.dijkstra(0, 4), Some(5));
+        assert_eq!(g.dijkstra(0, 3), Some(3));
+        #[allow(deprecated)]
+        let alias = g.djkstra(0, 4);
+        assert_eq!(alias, Some(5));
+
+        assert_eq!(
+            g.dijkstra_all(0),
+            vec![Some(0), Some(1), Some(10), Some(3), Some(5)]
+        );
+        assert_eq!(g.dijkstra_all(4), vec![None, None, None, None, Some(0)]);
+    }
+
+    #[test]
+    fn try_dijkstra_negative_edge() {
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 0);
+        g.add_edge(1, 2, 3);
+        assert_eq!(g.try_dijkstra(0, 2), Ok(Some(3)));
+        assert_eq!(g.try_dijkstra(2, 0), Ok(None));
+
+        g.add_edge(2, 0, -1);
+        let e = g.try_dijkstra(0, 2).unwrap_err();
+        assert_eq!(
+            e,
+            NegativeEdge {
+                from: 2,
+                to: 0,
+                cost: -1
+            }
+        );
+        assert_eq!(e.to_string(), "negative edge 2 -> 0 of cost -1");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "negative edge 0 -> 1")]
+    fn dijkstra_negative_edge_asserts() {
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, -2);
+        g.add_edge(1, 2, 1);
+        g.dijkstra_all(0);
+    }
+
+    #[test]
+    fn dijkstra_near_max_cost() {
+        let mut g = Dag::new(3);
+
+        g.add_edge(0, 1, i64::MAX - 1);
+        g.add_edge(1, 2, i64::MAX - 1);
+        g.add_edge(0, 2, 10);
+
+        assert_eq!(g.dijkstra(0, 1), Some(i64::MAX - 1));
+        assert_eq!(g.dijkstra(0, 2), Some(10));
+
+        let sp = g.floyd_warshall();
+        assert_eq!(sp[0][2], 10);
+        assert_eq!(sp[1][0], i64::MAX);
+    }
+
+    #[test]
+    fn astar_zero_heuristic_matches_dijkstra() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(40);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_index(20);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(4 * n) {
+                let cost = rng.gen_range(0..50) as i64;
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), cost);
+            }
+            let (from, to) = (rng.gen_index(n), rng.gen_index(n));
+            assert_eq!(g.astar(from, to, |_| 0), g.dijkstra(from, to));
+        }
+    }
+
+    #[test]
+    fn astar_manhattan_pops_fewer() {
+        let n = 60;
+        let grid = vec![vec!['.'; n]; n];
+        let (g, id) = crate::graph::grid::from_chars(&grid, |c| c == '.', false);
+        let goal = (n / 2, n - 1);
+        let manhattan = |v: usize| ((v / n).abs_diff(goal.0) + (v % n).abs_diff(goal.1)) as i64;
+
+        let (plain, plain_popped) = g.astar_counted(id(n / 2, 0), id(goal.0, goal.1), |_| 0);
+        let (guided, guided_popped) = g.astar_counted(id(n / 2, 0), id(goal.0, goal.1), manhattan);
+        assert_eq!(plain, Some(n as i64 - 1));
+        assert_eq!(guided, plain);
+        assert!(
+            guided_popped * 10 < plain_popped,
+            "{} {}",
+            guided_popped,
+            plain_popped
+        );
+        assert_eq!(g.astar(id(0, 0), id(goal.0, goal.1), manhattan), Some(89));
+    }
+
+    fn yen_graph() -> Dag {
+        // C=0 D=1 E=2 F=3 G=4 H=5
+        let mut g = Dag::new(6);
+        for (from, to, cost) in [
+            (0, 1, 3),
+            (0, 2, 2),
+            (1, 3, 4),
+            (2, 1, 1),
+            (2, 3, 2),
+            (2, 4, 3),
+            (3, 4, 2),
+            (3, 5, 1),
+            (4, 5, 2),
+        ] {
+            g.add_edge(from, to, cost);
+        }
+        g
+    }
+
+    #[test]
+    fn shortest_path() {
+        let g = yen_graph();
+        assert_eq!(g.shortest_path(0, 5), Some((5, vec![0, 2, 3, 5])));
+        assert_eq!(g.shortest_path(0, 0), Some((0, vec![0])));
+        assert_eq!(g.shortest_path(5, 0), None);
+    }
+
+    #[test]
+    fn shortest_path_edges() {
+        let mut g = Dag::new(3);
+        let a = g.add_edge(0, 1, 5);
+        let b = g.add_edge(0, 1, 2);
+        let c = g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 4);
+        assert_eq!(g.shortest_path_edges(0, 2), Some((3, vec![b, c])));
+        assert_eq!(g.shortest_path_edges(1, 1), Some((0, vec![])));
+        assert_eq!(g.shortest_path_edges(2, 0), None);
+        assert_eq!(g.edge(a), (0, 1, 5));
+    }
+
+    #[test]
+    fn edge_ids() {
+        let mut g = Dag::new(4);
+        let labels = ["a", "b", "c", "d", "e"];
+        let ids = [(0, 1, 3), (1, 2, 1), (0, 2, 1), (2, 3, 4), (3, 0, 2)]
+            .iter()
+            .map(|&(u, v, w)| g.add_edge(u, v, w))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, (0..5).map(EdgeId).collect::<Vec<_>>());
+        assert_eq!(
+            g.edge_ids(0).collect::<Vec<_>>(),
+            vec![EdgeId(0), EdgeId(2)]
+        );
+
+        // ids index any data kept per edge
+        let (total, chosen) = g.minimum_spanning_tree_edges().unwrap();
+        assert_eq!(
+            (total, chosen.clone()),
+            (4, vec![EdgeId(1), EdgeId(2), EdgeId(4)])
+        );
+        let used = chosen.iter().map(|id| labels[id.0]).collect::<Vec<_>>();
+        assert_eq!(used, vec!["b", "c", "e"]);
+
+        // removals neither renumber nor reorder the other edges
+        g.remove_edge(0, 1);
+        g.remove_edge(1, 2);
+        assert_eq!(g.edge(EdgeId(2)), (0, 2, 1));
+        assert_eq!(g.edge(EdgeId(4)), (3, 0, 2));
+        assert_eq!(g.edge_ids(0).collect::<Vec<_>>(), vec![EdgeId(2)]);
+        assert_eq!(g.minimum_spanning_tree_edges(), None);
+        assert_eq!(g.add_edge(1, 3, 7), EdgeId(5));
+        assert_eq!(
+            g.edges().collect::<Vec<_>>(),
+            vec![(0, 2, 1), (1, 3, 7), (2, 3, 4), (3, 0, 2)]
+        );
+        // 2 -> 3 closes the cycle 0 - 2 - 3 and is left out
+        assert_eq!(
+            g.minimum_spanning_tree_edges(),
+            Some((10, vec![EdgeId(2), EdgeId(4), EdgeId(5)]))
+        );
+    }
+
+    #[test]
+    fn remove_parallel_edge_by_id() {
+        let mut g = Dag::new(3);
+        let cheap = g.add_edge(0, 1, 1);
+        let dear = g.add_edge(0, 1, 10);
+        g.add_edge(1, 2, 1);
+        assert_eq!(g.dijkstra(0, 2), Some(2));
+
+        assert!(g.remove_edge_by_id(cheap));
+        assert!(!g.remove_edge_by_id(cheap));
+        assert!(g.is_removed(cheap) && !g.is_removed(dear));
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(1, 10)]);
+        assert_eq!(g.edges().collect::<Vec<_>>(), vec![(0, 1, 10), (1, 2, 1)]);
+        assert_eq!(g.dijkstra(0, 2), Some(11));
+        assert_eq!(g.shortest_path_edges(0, 2).unwrap().1[0], dear);
+        assert_eq!(format!("{:?}", g), "Dag {0: [(1, 10)], 1: [(2, 1)], 2: []}");
+
+        assert!(g.remove_edge(0, 1));
+        assert!(!g.remove_edge(0, 1));
+        assert_eq!(g.dijkstra(0, 2), None);
+        assert!(g.is_acyclic());
+        assert_eq!(g.edge(dear), (0, 1, 10));
+    }
+
+    #[test]
+    fn removed_ids_are_compacted() {
+        let mut g = Dag::new(2);
+        let ids = (0..1000).map(|i| g.add_edge(0, 1, i)).collect::<Vec<_>>();
+        for (i, &id) in ids.iter().enumerate().take(999) {
+            assert!(g.remove_edge(0, 1));
+            assert!(g.is_removed(id));
+            let live = 999 - i;
+            assert!(
+                g.adj[0].len() <= 2 * live,
+                "{} ids for {} edges",
+                g.adj[0].len(),
+                live
+            );
+        }
+        assert_eq!(g.edge_ids(0).collect::<Vec<_>>(), vec![ids[999]]);
+        assert_eq!(g.edge(ids[0]), (0, 1, 0));
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn remove_edge_in_cycle() {
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 1);
+        let back = g.add_edge(1, 0, 1);
+        g.add_edge(1, 2, 1);
+        assert!(g.find_cycle().is_some());
+        g.remove_edge_by_id(back);
+        assert_eq!(g.find_cycle(), None);
+        assert_eq!(g.topological_sort(), Some(vec![0, 1, 2]));
+        assert_eq!(g.scc().len(), 3);
+    }
+
+    #[test]
+    fn k_shortest_paths() {
+        let g = yen_graph();
+        let paths = g.k_shortest_paths(0, 5, 3);
+        assert_eq!(paths[0], (5, vec![0, 2, 3, 5]));
+        assert_eq!(paths[1], (7, vec![0, 2, 4, 5]));
+        assert_eq!(paths[2].0, 8);
+
+        // all 7 simple paths, ties at 8 in any order
+        let paths = g.k_shortest_paths(0, 5, 100);
+        let costs = paths.iter().map(|(c, _)| *c).collect::<Vec<_>>();
+        assert_eq!(costs, vec![5, 7, 8, 8, 8, 11, 11]);
+        for (cost, path) in &paths {
+            assert_eq!(g.path_cost(path), Cost::Finite(*cost));
+        }
+        let distinct = paths.iter().map(|(_, p)| p).collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), paths.len());
+
+        assert!(g.k_shortest_paths(5, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn k_shortest_paths_parallel_edges() {
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 3);
+        assert_eq!(
+            g.k_shortest_paths(0, 2, 5),
+            vec![(2, vec![0, 1, 2]), (3, vec![0, 2])]
+        );
+    }
+
+    #[test]
+    fn dominator_tree() {
+        // diamond 0 -> {1, 2} -> 3 -> 4, vertex 5 unreachable
+        let mut g = Dag::new(6);
+        for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)] {
+            g.add_edge(from, to, 1);
+        }
+        assert_eq!(
+            g.dominator_tree(0),
+            vec![None, Some(0), Some(0), Some(0), Some(3), None]
+        );
+
+        // the classic example from Lengauer and Tarjan's paper, R=0 A..L=1..12
+        let mut g = Dag::new(13);
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 4),
+            (2, 1),
+            (2, 4),
+            (2, 5),
+            (3, 6),
+            (3, 7),
+            (4, 12),
+            (5, 8),
+            (6, 9),
+            (7, 9),
+            (7, 10),
+            (8, 5),
+            (8, 11),
+            (9, 11),
+            (10, 9),
+            (11, 0),
+            (11, 9),
+            (12, 8),
+        ];
+        for (from, to) in edges {
+            g.add_edge(from, to, 1);
+        }
+        let idom = g.dominator_tree(0);
+        let expected = [0, 0, 0, 0, 0, 0, 3, 3, 0, 0, 7, 0, 4];
+        for v in 1..13 {
+            assert_eq!(idom[v], Some(expected[v]), "vertex {v}");
+        }
+    }
+
+    #[test]
+    fn dominator_tree_brute_force() {
+        use crate::util::rand::XorShift64;
+
+        fn reachable(g: &Dag, root: usize, removed: usize) -> Vec<bool> {
+            let mut seen = vec![false; g.len()];
+            if root == removed {
+                return seen;
+            }
+            seen[root] = true;
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for e in g.out_edges(v) {
+                    if e.to != removed && !seen[e.to] {
+                        seen[e.to] = true;
+                        stack.push(e.to);
+                    }
+                }
+            }
+            seen
+        }
+
+        let mut rng = XorShift64::new(11);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(50);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(3 * n) {
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), 1);
+            }
+            let root = rng.gen_index(n);
+            let idom = g.dominator_tree(root);
+            let base = reachable(&g, root, usize::MAX);
+
+            for v in 0..n {
+                if v == root || !base[v] {
+                    assert_eq!(idom[v], None);
+                    continue;
+                }
+                // strict dominators of v, by removing each candidate
+                let brute = (0..n)
+                    .filter(|&d| d != v && !reachable(&g, root, d)[v])
+                    .collect::<HashSet<_>>();
+                let mut chain = HashSet::new();
+                let mut x = v;
+                while let Some(d) = idom[x] {
+                    chain.insert(d);
+                    x = d;
+                }
+                assert_eq!(chain, brute);
+            }
+        }
+    }
+
+    #[test]
+    fn dijkstra_generic_weight() {
+        let mut g = Dag::<u32>::with_size(4);
+        g.add_edge(0, 1, u32::MAX);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 3, 3);
+        g.add_edge(3, 2, 4);
+        assert_eq!(g.dijkstra(0, 1), Some(u32::MAX));
+        // the overflowing path through 1 is skipped
+        assert_eq!(g.dijkstra(0, 2), Some(7));
+        assert_eq!(g.dijkstra(2, 0), None);
+
+        let mut g = Dag::<u64>::with_size(2);
+        g.add_edge(0, 1, 1 << 63);
+        assert_eq!(g.dijkstra(0, 1), Some(1 << 63));
+        assert_eq!(
+            format!("{:?}", g),
+            "Dag {0: [(1, 9223372036854775808)], 1: []}"
+        );
+    }
+
+    #[test]
+    fn bellman_ford_negative_edges() {
+        let mut g = Dag::new(5);
+        g.add_edge(0, 1, 4);
+        g.add_edge(0, 2, 2);
+        g.add_edge(2, 1, -3);
+        g.add_edge(1, 3, 2);
+        g.add_edge(3, 2, 1);
+
+        assert_eq!(
+            g.bellman_ford(0),
+            Ok(vec![Some(0), Some(-1), Some(2), Some(1), None])
+        );
+        assert_eq!(g.bellman_ford(4), Ok(vec![None, None, None, None, Some(0)]));
+    }
+
+    #[test]
+    fn bellman_ford_reachable_negative_cycle() {
+        // 1 -> 2 -> 1 costs -1, 3 and 4 are behind it and 5 is unreachable
+        let mut g = Dag::new(6);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 1, -2);
+        g.add_edge(2, 3, 5);
+        g.add_edge(0, 4, 3);
+        g.add_edge(3, 4, -100);
+
+        let err = g.bellman_ford(0).unwrap_err();
+        assert_eq!(err.unbounded, vec![false, true, true, true, true, false]);
+        assert_eq!(err.dist, vec![Some(0), None, None, None, None, None]);
+        assert_eq!(err.to_string(), "negative cycle makes 4 vertices unbounded");
+    }
+
+    #[test]
+    fn bellman_ford_unreachable_negative_cycle() {
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, -5);
+        g.add_edge(2, 3, -1);
+        g.add_edge(3, 2, -1);
+        g.add_edge(3, 1, -10);
+
+        assert_eq!(g.bellman_ford(0), Ok(vec![Some(0), Some(-5), None, None]));
+        assert!(g.bellman_ford(2).is_err());
+    }
+
+    #[test]
+    fn bellman_ford_overflowing_negative_cycle() {
+        // the cycle 0 -> 1 -> 0 overflows below i64::MIN on its second lap,
+        // and 2, 3 only add rounds in which nothing else improves
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, i64::MIN / 2);
+        g.add_edge(1, 0, i64::MIN / 2);
+
+        let err = g.bellman_ford(0).unwrap_err();
+        assert_eq!(err.unbounded, vec![true, true, false, false]);
+        assert!(g.johnson().is_err());
+    }
+
+    #[test]
+    fn bellman_ford_matches_dijkstra() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(19);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..8) as usize;
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(3 * n) {
+                let (u, v) = (rng.gen_index(n), rng.gen_index(n));
+                // non-negative, so no negative cycle
+                g.add_edge(u, v, rng.gen_range(0..20) as i64);
+            }
+            for s in 0..n {
+                assert_eq!(g.bellman_ford(s), Ok(g.dijkstra_all(s)));
+            }
+        }
+    }
+
+    #[test]
+    fn bfs01() {
+        // the 0-edge path 0 -> 2 -> 3 -> 1 is found after 1 is first reached by 0 -> 1
+        let mut g = Dag::new(5);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 0);
+        g.add_edge(2, 3, 0);
+        g.add_edge(3, 1, 0);
+        g.add_edge(1, 4, 1);
+        assert_eq!(
+            g.bfs01(0),
+            vec![Some(0), Some(0), Some(0), Some(0), Some(1)]
+        );
+        assert_eq!(g.bfs01(4), vec![None, None, None, None, Some(0)]);
+    }
+
+    #[test]
+    fn bfs01_matches_dijkstra() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(25);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(30);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(4 * n) {
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), rng.gen_index(2) as i64);
+            }
+            let s = rng.gen_index(n);
+            assert_eq!(g.bfs01(s), g.dijkstra_all(s));
+        }
+    }
+
+    #[test]
+    fn bfs() {
+        let g = yen_graph();
+        assert_eq!(
+            g.bfs(0),
+            vec![Some(0), Some(1), Some(1), Some(2), Some(2), Some(3)]
+        );
+        assert_eq!(g.bfs(5), vec![None, None, None, None, None, Some(0)]);
+        assert_eq!(g.bfs_path(0, 0), Some(vec![0]));
+        assert_eq!(g.bfs_path(5, 0), None);
+
+        let path = g.bfs_path(0, 5).unwrap();
+        assert_eq!(path.len(), 4);
+        assert_eq!((path[0], path[3]), (0, 5));
+    }
+
+    #[test]
+    fn bfs_matches_dijkstra() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(26);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(30);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(3 * n) {
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), 1);
+            }
+            let s = rng.gen_index(n);
+            let dist = g.bfs(s);
+            let expected = g.dijkstra_all(s);
+            for t in 0..n {
+                assert_eq!(dist[t].map(i64::from), expected[t]);
+                let Some(path) = g.bfs_path(s, t) else {
+                    assert_eq!(dist[t], None);
+                    continue;
+                };
+                assert_eq!(path.len() as u32, dist[t].unwrap() + 1);
+                assert_eq!((path[0], path[path.len() - 1]), (s, t));
+                for w in path.windows(2) {
+                    assert!(g.out_edges(w[0]).any(|e| e.to == w[1]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn multi_source() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(27);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(20);
+            let mut g = Dag::new(n);
+            for _ in 0..rng.gen_index(3 * n) {
+                let cost = rng.gen_range(0..10) as i64;
+                g.add_edge(rng.gen_index(n), rng.gen_index(n), cost);
+            }
+            let sources = (0..1 + rng.gen_index(3))
+                .map(|_| (rng.gen_index(n), rng.gen_range(0..5) as i64))
+                .collect::<Vec<_>>();
+
+            let single = sources
+                .iter()
+                .map(|&(s, c)| g.dijkstra_all(s).into_iter().map(move |d| d.map(|d| d + c)))
+                .fold(vec![None; n], |acc, dist| {
+                    acc.into_iter()
+                        .zip(dist)
+                        .map(|(a, d)| a.into_iter().chain(d).min())
+                        .collect()
+                });
+            assert_eq!(g.dijkstra_multi(&sources), single);
+
+            let vertices = sources.iter().map(|&(s, _)| s).collect::<Vec<_>>();
+            let single = vertices
+                .iter()
+                .map(|&s| g.bfs(s))
+                .fold(vec![None; n], |acc, dist| {
+                    acc.into_iter()
+                        .zip(dist)
+                        .map(|(a, d)| a.into_iter().chain(d).min())
+                        .collect()
+                });
+            assert_eq!(g.bfs_multi(&vertices), single);
+        }
+    }
+
+    #[test]
+    fn multi_source_initial_cost() {
+        // 0 -> 2 -> 3, 1 -> 3, source 1 starts late and 0 is given twice
+        let mut g = Dag::new(4);
+        g.add_edge(0, 2, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(1, 3, 1);
+        assert_eq!(
+            g.dijkstra_multi(&[(0, 5), (1, 10), (0, 0)]),
+            vec![Some(0), Some(10), Some(1), Some(2)]
+        );
+        assert_eq!(
+            g.dijkstra_multi(&[(1, 0), (0, 5)]),
+            vec![Some(5), Some(0), Some(6), Some(1)]
+        );
+        assert_eq!(
+            g.bfs_multi(&[0, 1]),
+            vec![Some(0), Some(0), Some(1), Some(1)]
+        );
+        assert_eq!(g.dijkstra_multi(&[]), vec![None; 4]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "neither 0 nor 1")]
+    fn bfs01_other_cost() {
+        let mut g = Dag::new(2);
+        g.add_edge(0, 1, 2);
+        g.bfs01(0);
+    }
+
+    fn is_topological_order(g: &Dag, order: &[usize]) -> bool {
+        let mut position = vec![usize::MAX; g.len()];
+        for (i, &v) in order.iter().enumerate() {
+            position[v] = i;
+        }
+        order.len() == g.len()
+            && position.iter().all(|&p| p != usize::MAX)
+            && g.all_edges().all(|e| position[e.from] < position[e.to])
+    }
+
+    #[test]
+    fn topological_sort() {
+        //   5 -> 0 <- 4
+        //   |         |
+        //   v         v
+        //   2 -> 3 -> 1
+        let mut g = Dag::new(6);
+        g.add_edge(5, 2, 1);
+        g.add_edge(5, 0, 1);
+        g.add_edge(4, 0, 1);
+        g.add_edge(4, 1, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 1, 1);
+
+        let order = g.topological_sort().unwrap();
+        assert!(is_topological_order(&g, &order));
+        assert_eq!(
+            g.topological_sort_lexicographic(),
+            Some(vec![4, 5, 0, 2, 3, 1])
+        );
+
+        g.add_edge(1, 5, 1);
+        assert_eq!(g.topological_sort(), None);
+        assert_eq!(g.topological_sort_lexicographic(), None);
+    }
+
+    #[test]
+    fn topological_sort_self_loop_and_empty() {
+        let mut g = Dag::new(2);
+        assert!(is_topological_order(&g, &g.topological_sort().unwrap()));
+        g.add_edge(1, 1, 0);
+        assert_eq!(g.topological_sort(), None);
+
+        assert_eq!(Dag::new(0).topological_sort(), Some(vec![]));
+    }
+
+    fn assert_cycle(g: &Dag, cycle: &[usize]) {
+        assert!(!cycle.is_empty());
+        for (i, &v) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(g.out_edges(v).any(|e| e.to == next), "{} -> {}", v, next);
+        }
+    }
+
+    #[test]
+    fn find_cycle() {
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 3, 1);
+        g.add_edge(2, 3, 1);
+        assert!(g.is_acyclic());
+        assert_eq!(g.find_cycle(), None);
+
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 1);
+        g.add_edge(2, 2, 1);
+        assert_eq!(g.find_cycle(), Some(vec![2]));
+        assert!(!g.is_acyclic());
+
+        let mut g = Dag::new(3);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 1, 1);
+        let cycle = g.find_cycle().unwrap();
+        assert_eq!(cycle, vec![1, 2]);
+        assert_cycle(&g, &cycle);
+    }
+
+    #[test]
+    fn find_cycle_long_path() {
+        let n = 200_000;
+        let mut g = Dag::new(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v, 1);
+        }
+        assert!(g.is_acyclic());
+
+        g.add_edge(n - 1, 10, 1);
+        let cycle = g.find_cycle().unwrap();
+        assert_eq!(cycle.len(), n - 10);
+        assert_cycle(&g, &cycle);
+    }
+
+    #[test]
+    fn scc() {
+        // 0 <-> 1 -> 2 -> 3 -> 2
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 0, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 2, 1);
+
+        let scc = g.scc();
+        assert_eq!(scc.components, vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(scc.component_id, vec![0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn condensation() {
+        // cycles 0 -> 1 -> 2 -> 0 and 3 -> 4 -> 5 -> 3 joined by two edges, with a self loop
+        let mut g = Dag::new(6);
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (1, 1)] {
+            g.add_edge(from, to, 1);
+        }
+        g.add_edge(2, 4, 7);
+        g.add_edge(0, 3, 5);
+
+        let (dag, id) = g.condensation();
+        assert_eq!(id, vec![0, 0, 0, 1, 1, 1]);
+        assert_eq!(format!("{:?}", dag), "Dag {0: [(1, 5)], 1: []}");
+        assert!(dag.is_acyclic());
+
+        let (dag, id) = Dag::new(2).condensation();
+        assert_eq!(dag.len(), 2);
+        assert_ne!(id[0], id[1]);
+    }
+
+    #[test]
+    fn longest_path() {
+        let mut g = Dag::new(4);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(1, 3, 4);
+        g.add_edge(2, 1, 2);
+        g.add_edge(2, 3, 3);
+
+        // 0 -> 1 -> 3 weighs more, 0 -> 2 -> 1 -> 3 has more edges
+        assert_eq!(g.longest_path(), 9);
+        assert_eq!(g.longest_path_len(), 3);
+        assert_eq!(
+            g.longest_path_from(2),
+            vec![None, Some(2), Some(0), Some(6)]
+        );
+    }
+
+    #[test]
+    fn longest_path_disconnected() {
+        // 0 -> 1 and 2 -> 3 -> 4 with negative costs, 5 isolated
+        let mut g = Dag::new(6);
+        g.add_edge(0, 1, 7);
+        g.add_edge(2, 3, -1);
+        g.add_edge(3, 4, 3);
+
+        assert_eq!(g.longest_path(), 7);
+        assert_eq!(g.longest_path_len(), 2);
+        assert_eq!(
+            g.longest_path_from(2),
+            vec![None, None, Some(0), Some(-1), Some(2), None]
+        );
+        assert_eq!(Dag::new(0).longest_path(), 0);
+    }
+
+    #[test]
+    fn longest_path_long_chain() {
+        let n = 300_000;
+        let mut g = Dag::<u8>::with_size(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v, 1);
+        }
+        assert_eq!(g.longest_path_len(), n - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle")]
+    fn longest_path_cycle() {
+        let mut g = Dag::new(2);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 0, 1);
+        g.longest_path();
+    }
+
+    #[test]
+    fn count_paths() {
+        //   0 -> 1 -> 3
+        //   |    |    ^
+        //   v    v    |
+        //   2 -------->
+        let mut g = Dag::new(5);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(1, 3, 1);
+        g.add_edge(2, 3, 1);
+
+        assert_eq!(g.count_paths(0, 3, 1_000_000_007), Some(3));
+        assert_eq!(
+            g.count_paths_from(0, 1_000_000_007),
+            vec![Some(1), Some(1), Some(2), Some(3), Some(0)]
+        );
+        assert_eq!(g.count_paths(3, 0, 1_000_000_007), Some(0));
+
+        // parallel edges are distinct paths
+        g.add_edge(2, 3, 1);
+        assert_eq!(g.count_paths(0, 3, 1_000_000_007), Some(5));
+    }
+
+    #[test]
+    fn count_paths_cycle() {
+        // 0 -> 1 <-> 2 -> 3, 0 -> 4, and a cycle 5 <-> 6 -> 4 unreachable from 0
+        let mut g = Dag::new(7);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 1, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(0, 4, 1);
+        g.add_edge(5, 6, 1);
+        g.add_edge(6, 5, 1);
+        g.add_edge(6, 4, 1);
+
+        assert_eq!(
+            g.count_paths_from(0, 1_000_000_007),
+            vec![Some(1), None, None, None, Some(1), Some(0), Some(0)]
+        );
+        assert_eq!(g.count_paths(5, 4, 1_000_000_007), None);
+        assert_eq!(g.count_paths(5, 5, 1_000_000_007), None);
+        assert_eq!(g.count_paths(5, 0, 1_000_000_007), Some(0));
+    }
+
+    #[test]
+    fn count_paths_modulo() {
+        // 60 layers of two parallel edges give 2^60 paths
+        let mut g = Dag::new(61);
+        for v in 0..60 {
+            g.add_edge(v, v + 1, 1);
+            g.add_edge(v, v + 1, 1);
+        }
+        let modulus = 1_000_000_007;
+        assert_eq!(g.count_paths(0, 60, modulus), Some((1 << 60) % modulus));
+        assert_eq!(g.count_paths(0, 60, 1), Some(0));
+        assert_eq!(g.count_paths(0, 0, 1), Some(0));
     }
 }
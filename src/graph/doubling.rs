@@ -0,0 +1,147 @@
+//! Binary lifting over a functional graph, where each vertex has exactly one outgoing edge.
+
+/// Vertex reached after `k` steps of `next` in `O(log k)`.
+#[derive(Clone, Debug)]
+pub struct Doubling {
+    // jump[i][v] is the vertex 2^i steps after v
+    jump: Vec<Vec<usize>>,
+}
+
+impl Doubling {
+    /// Table for any number of steps up to `max_k`.
+    pub fn new(next: &[usize], max_k: u64) -> Self {
+        let levels = (u64::BITS - max_k.leading_zeros()).max(1) as usize;
+        let mut jump = vec![next.to_vec()];
+        for i in 1..levels {
+            let prev = &jump[i - 1];
+            let doubled = prev.iter().map(|&v| prev[v]).collect();
+            jump.push(doubled);
+        }
+        Self { jump }
+    }
+
+    /// Vertex after `k` steps from `start`, `k` must be at most `max_k`.
+    pub fn kth(&self, mut start: usize, k: u64) -> usize {
+        assert!(
+            k.checked_shr(self.jump.len() as u32).unwrap_or(0) == 0,
+            "{} steps exceed the table",
+            k
+        );
+        for (i, jump) in self.jump.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                start = jump[start];
+            }
+        }
+        start
+    }
+}
+
+/// [`Doubling`] that also folds the value of every vertex left on the way by a monoid.
+pub struct DoublingFold<T, F> {
+    // (vertex 2^i steps after v, fold of the 2^i vertices from v)
+    jump: Vec<Vec<(usize, T)>>,
+    e: T,
+    op: F,
+}
+
+impl<T, F> DoublingFold<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    /// `op` must be associative with identity `e`, it needn't be commutative.
+    pub fn new(next: &[usize], value: &[T], max_k: u64, e: T, op: F) -> Self {
+        assert_eq!(next.len(), value.len());
+        let levels = (u64::BITS - max_k.leading_zeros()).max(1) as usize;
+        let mut jump = vec![next.iter().copied().zip(value.iter().cloned()).collect()];
+        for i in 1..levels {
+            let prev: &Vec<(usize, T)> = &jump[i - 1];
+            let doubled = prev
+                .iter()
+                .map(|(mid, a)| {
+                    let (to, b) = &prev[*mid];
+                    (*to, op(a, b))
+                })
+                .collect();
+            jump.push(doubled);
+        }
+        Self { jump, e, op }
+    }
+
+    /// Vertex after `k` steps from `start` and the fold of the `k` vertices left,
+    /// `start` first and the returned vertex excluded.
+    pub fn fold(&self, mut start: usize, k: u64) -> (usize, T) {
+        assert!(
+            k.checked_shr(self.jump.len() as u32).unwrap_or(0) == 0,
+            "{} steps exceed the table",
+            k
+        );
+        let mut acc = self.e.clone();
+        for (i, jump) in self.jump.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                let (to, value) = &jump[start];
+                acc = (self.op)(&acc, value);
+                start = *to;
+            }
+        }
+        (start, acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::rand::XorShift64;
+
+    #[test]
+    fn rho() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2, a tail of 2 into a cycle of 4
+        let next = [1, 2, 3, 4, 5, 2];
+        let d = Doubling::new(&next, u64::MAX);
+        assert_eq!(d.kth(0, 0), 0);
+        assert_eq!(d.kth(0, 1), 1);
+        assert_eq!(d.kth(0, 5), 5);
+        assert_eq!(d.kth(0, 6), 2);
+        assert_eq!(d.kth(3, 4), 3);
+        // (10^18 - 2) % 4 == 2
+        assert_eq!(d.kth(0, 1_000_000_000_000_000_000), 4);
+        // (2^64 - 1 - 2) % 4 == 1
+        assert_eq!(d.kth(0, u64::MAX), 3);
+
+        let single = Doubling::new(&[0], 0);
+        assert_eq!(single.kth(0, 0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn beyond_max_k() {
+        Doubling::new(&[1, 0], 5).kth(0, 8);
+    }
+
+    #[test]
+    fn fold_against_walk() {
+        let mut rng = XorShift64::new(38);
+        for n in 1..20 {
+            let next = (0..n).map(|_| rng.gen_index(n)).collect::<Vec<_>>();
+            let value = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<_>>();
+            let d = Doubling::new(&next, 100);
+            let sum = DoublingFold::new(&next, &value, 100, 0, |a, b| a + b);
+            // the order of visits matters for a non commutative fold
+            let labels = (0..n).map(|v| v.to_string()).collect::<Vec<_>>();
+            let concat =
+                DoublingFold::new(&next, &labels, 100, String::new(), |a, b| a.clone() + b);
+
+            for start in 0..n {
+                let (mut v, mut total, mut visited) = (start, 0, String::new());
+                for k in 0..=100 {
+                    assert_eq!(d.kth(start, k), v);
+                    assert_eq!(sum.fold(start, k), (v, total));
+                    assert_eq!(concat.fold(start, k), (v, visited.clone()));
+                    total += value[v];
+                    visited += &labels[v];
+                    v = next[v];
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::collections::unionfind::UnionFind;
+
+/// Vertices of a path using every edge exactly once by Hierholzer's algorithm, `None` if there is none.
+///
+/// The path has `edges.len() + 1` vertices and is a circuit when every vertex is balanced.
+/// It starts at the vertex with one more outgoing edge (directed) or at an odd vertex (undirected)
+/// if any, so an Eulerian circuit is preferred. Without edges it is the single vertex `0`.
+pub fn eulerian_path(n: usize, edges: &[(usize, usize)], directed: bool) -> Option<Vec<usize>> {
+    if edges.is_empty() {
+        return (n > 0).then(|| vec![0]);
+    }
+
+    // (to, edge id)
+    let mut adj = vec![vec![]; n];
+    // out - in for directed graphs, the degree for undirected ones
+    let mut balance = vec![0i64; n];
+    let mut uf = UnionFind::new(n);
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        adj[u].push((v, id));
+        uf.union(u, v);
+        if directed {
+            balance[u] += 1;
+            balance[v] -= 1;
+        } else {
+            adj[v].push((u, id));
+            balance[u] += 1;
+            balance[v] += 1;
+        }
+    }
+
+    let start = if directed {
+        // balances sum to 0, so the other unbalanced vertex has -1
+        let start = (0..n).find(|&v| balance[v] == 1);
+        let unbalanced = (0..n).filter(|&v| balance[v] != 0).count();
+        if unbalanced != 2 * start.is_some() as usize {
+            return None;
+        }
+        start
+    } else {
+        let odd = (0..n).filter(|&v| balance[v] % 2 == 1).collect::<Vec<_>>();
+        if !odd.is_empty() && odd.len() != 2 {
+            return None;
+        }
+        odd.first().copied()
+    };
+    let start = start.unwrap_or(edges[0].0);
+    if edges.iter().any(|&(u, _)| !uf.equiv(start, u)) {
+        return None;
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut next = vec![0; n];
+    let mut stack = vec![start];
+    let mut path = Vec::with_capacity(edges.len() + 1);
+    while let Some(&v) = stack.last() {
+        // skip edges already walked from the other end
+        while next[v] < adj[v].len() && used[adj[v][next[v]].1] {
+            next[v] += 1;
+        }
+        if let Some(&(to, id)) = adj[v].get(next[v]) {
+            used[id] = true;
+            stack.push(to);
+        } else {
+            path.push(v);
+            stack.pop();
+        }
+    }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::rand::XorShift64;
+
+    /// Whether `path` walks every edge exactly once.
+    fn walks_all(edges: &[(usize, usize)], path: &[usize], directed: bool) -> bool {
+        let mut rest = edges.to_vec();
+        path.len() == edges.len() + 1
+            && path.windows(2).all(|w| {
+                let found = rest
+                    .iter()
+                    .position(|&e| e == (w[0], w[1]) || (!directed && e == (w[1], w[0])));
+                found.map(|i| rest.swap_remove(i)).is_some()
+            })
+    }
+
+    #[test]
+    fn directed_cycle() {
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 0)];
+        let path = eulerian_path(4, &edges, true).unwrap();
+        assert_eq!(path, vec![0, 1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn directed_path() {
+        // two loops through 1, ending at 3
+        let edges = [(1, 2), (2, 1), (0, 1), (1, 3), (1, 4), (4, 1)];
+        let path = eulerian_path(5, &edges, true).unwrap();
+        assert_eq!((path[0], path[6]), (0, 3));
+        assert!(walks_all(&edges, &path, true));
+        assert_eq!(eulerian_path(3, &[(0, 1), (0, 2)], true), None);
+    }
+
+    #[test]
+    fn undirected_two_odd() {
+        // a square with a diagonal, 1 and 3 are odd
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 0), (1, 3)];
+        let path = eulerian_path(4, &edges, false).unwrap();
+        assert!(path[0] == 1 || path[0] == 3);
+        assert_eq!(path[0] + path[5], 4);
+        assert!(walks_all(&edges, &path, false));
+    }
+
+    #[test]
+    fn degree_condition() {
+        // a star with three leaves has four odd vertices
+        let edges = [(0, 1), (0, 2), (0, 3)];
+        assert_eq!(eulerian_path(4, &edges, false), None);
+        assert_eq!(eulerian_path(4, &edges, true), None);
+    }
+
+    #[test]
+    fn disconnected() {
+        let edges = [(0, 1), (1, 0), (2, 3), (3, 2)];
+        assert_eq!(eulerian_path(4, &edges, true), None);
+        assert_eq!(eulerian_path(4, &edges, false), None);
+        // isolated vertices don't matter
+        let path = eulerian_path(6, &edges[..2], false).unwrap();
+        assert!(walks_all(&edges[..2], &path, false));
+        assert_eq!(eulerian_path(3, &[], false), Some(vec![0]));
+    }
+
+    #[test]
+    fn random_circuits() {
+        let mut rng = XorShift64::new(39);
+        for _ in 0..100 {
+            // a closed walk always has an Eulerian circuit
+            let n = 1 + rng.gen_index(8);
+            let mut walk = vec![0];
+            for _ in 0..rng.gen_index(30) {
+                walk.push(rng.gen_index(n));
+            }
+            walk.push(0);
+            let mut edges = walk.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>();
+            rng.shuffle(&mut edges);
+            for directed in [true, false] {
+                let path = eulerian_path(n, &edges, directed).unwrap();
+                assert!(walks_all(&edges, &path, directed));
+                assert_eq!(path.first(), path.last());
+            }
+        }
+    }
+
+    #[test]
+    fn long_cycle() {
+        let n = 200_000;
+        let edges = (0..n).map(|v| (v, (v + 1) % n)).collect::<Vec<_>>();
+        let path = eulerian_path(n, &edges, false).unwrap();
+        assert_eq!(path.len(), n + 1);
+    }
+}
@@ -0,0 +1,119 @@
+//! Graphs of `h x w` character grids, one vertex per cell.
+
+use super::dag::Dag;
+
+const DIRECTIONS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// Graph with an edge of cost 1 in both directions between adjacent passable cells,
+/// and the mapping from `(row, column)` to its vertex.
+///
+/// Cells are adjacent by their sides, or also by their corners with `connect8`.
+/// Walls are vertices without edges.
+///
+/// # Panics
+/// If the rows don't all have the same length.
+pub fn from_chars<F>(
+    grid: &[Vec<char>],
+    passable: F,
+    connect8: bool,
+) -> (Dag, impl Fn(usize, usize) -> usize)
+where
+    F: Fn(char) -> bool,
+{
+    let h = grid.len();
+    let w = grid.first().map_or(0, Vec::len);
+    if let Some(r) = grid.iter().position(|row| row.len() != w) {
+        panic!(
+            "ragged grid, row {} has {} cells but row 0 has {}",
+            r,
+            grid[r].len(),
+            w
+        );
+    }
+    let index = move |r: usize, c: usize| r * w + c;
+    let directions: &[(isize, isize)] = if connect8 {
+        &DIRECTIONS_8
+    } else {
+        &DIRECTIONS_4
+    };
+
+    let mut g = Dag::new(h * w);
+    for r in 0..h {
+        for c in 0..w {
+            if !passable(grid[r][c]) {
+                continue;
+            }
+            for &(dr, dc) in directions {
+                let (Some(nr), Some(nc)) = (r.checked_add_signed(dr), c.checked_add_signed(dc))
+                else {
+                    continue;
+                };
+                if nr < h && nc < w && passable(grid[nr][nc]) {
+                    g.add_edge(index(r, c), index(nr, nc), 1);
+                }
+            }
+        }
+    }
+    (g, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    #[test]
+    fn maze() {
+        let grid = chars(&["S.#", "#.#", "..G"]);
+        let (g, id) = from_chars(&grid, |c| c != '#', false);
+        let dist = g.bfs(id(0, 0));
+        assert_eq!(dist[id(2, 2)], Some(4));
+        assert_eq!(dist[id(2, 0)], Some(4));
+        assert_eq!(dist[id(0, 2)], None);
+        assert_eq!(g.bfs_path(id(0, 0), id(2, 2)).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn all_walls() {
+        let grid = chars(&["##", "##"]);
+        let (g, id) = from_chars(&grid, |c| c == '.', true);
+        assert_eq!(id(1, 1), 3);
+        assert_eq!(g.bfs(0), vec![Some(0), None, None, None]);
+        assert_eq!(format!("{:?}", g), "Dag {0: [], 1: [], 2: [], 3: []}");
+    }
+
+    #[test]
+    fn diagonal() {
+        // only corners touch
+        let grid = chars(&[".#.", "#.#", ".#."]);
+        let (g, id) = from_chars(&grid, |c| c == '.', false);
+        assert_eq!(g.bfs(id(1, 1)).iter().flatten().count(), 1);
+
+        let (g, id) = from_chars(&grid, |c| c == '.', true);
+        let dist = g.bfs(id(0, 0));
+        assert_eq!(dist[id(1, 1)], Some(1));
+        assert_eq!(dist[id(2, 2)], Some(2));
+        assert_eq!(dist[id(0, 2)], Some(2));
+        assert_eq!(dist[id(0, 1)], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ragged grid, row 1 has 2 cells but row 0 has 3")]
+    fn ragged() {
+        let grid = chars(&["...", "..", "..."]);
+        let _ = from_chars(&grid, |c| c == '.', false);
+    }
+}
@@ -0,0 +1,219 @@
+use std::ops::Range;
+
+use super::tree::Tree;
+
+/// Heavy-light decomposition of a rooted [`Tree`].
+///
+/// Vertices are numbered so that each heavy path and each subtree is a contiguous range
+/// of positions, and any path splits into `O(log n)` ranges.
+/// For values on edges, put the value of the edge to the parent at the position of the child.
+#[derive(Clone, Debug)]
+pub struct Hld {
+    pos: Vec<usize>,
+    size: Vec<usize>,
+    // top vertex of the heavy path of each vertex
+    head: Vec<usize>,
+    // parent of each vertex, the root is its own parent
+    parent: Vec<usize>,
+}
+
+impl Hld {
+    pub fn new(tree: &Tree) -> Self {
+        let n = tree.len();
+        let root = tree.root();
+        let mut parent = vec![root; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for c in tree.children(v) {
+                parent[c] = v;
+                stack.push(c);
+            }
+        }
+
+        let mut size = vec![1; n];
+        let mut heavy = vec![usize::MAX; n];
+        for &v in order.iter().rev() {
+            if v != root {
+                size[parent[v]] += size[v];
+            }
+        }
+        for &v in &order {
+            heavy[v] = tree
+                .children(v)
+                .max_by_key(|&c| size[c])
+                .unwrap_or(usize::MAX);
+        }
+
+        // dfs visiting the heavy child first, so heavy paths are contiguous
+        let mut pos = vec![0; n];
+        let mut head = vec![root; n];
+        let mut now = 0;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            pos[v] = now;
+            now += 1;
+            for c in tree.children(v) {
+                if c != heavy[v] {
+                    head[c] = c;
+                    stack.push(c);
+                }
+            }
+            if heavy[v] != usize::MAX {
+                head[heavy[v]] = head[v];
+                stack.push(heavy[v]);
+            }
+        }
+
+        Self {
+            pos,
+            size,
+            head,
+            parent,
+        }
+    }
+
+    /// Position of `v`.
+    pub fn pos(&self, v: usize) -> usize {
+        self.pos[v]
+    }
+
+    /// Positions of the subtree of `v`.
+    pub fn subtree_range(&self, v: usize) -> Range<usize> {
+        self.pos[v]..self.pos[v] + self.size[v]
+    }
+
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.pos[self.head[u]] < self.pos[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            u = self.parent[self.head[u]];
+        }
+        if self.pos[u] < self.pos[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// Positions of the vertices on the path between `u` and `v`, both ends and the lca included.
+    pub fn path_ranges(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        self.ranges(u, v, true)
+    }
+
+    /// Positions of the edges on the path between `u` and `v`, which excludes the lca.
+    pub fn edge_path_ranges(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        self.ranges(u, v, false)
+    }
+
+    fn ranges(&self, mut u: usize, mut v: usize, include_lca: bool) -> Vec<Range<usize>> {
+        let mut ranges = vec![];
+        while self.head[u] != self.head[v] {
+            if self.pos[self.head[u]] < self.pos[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            ranges.push(self.pos[self.head[u]]..self.pos[u] + 1);
+            u = self.parent[self.head[u]];
+        }
+        // u and v are on the heavy path of the lca now
+        let (lo, hi) = if self.pos[u] < self.pos[v] {
+            (self.pos[u], self.pos[v])
+        } else {
+            (self.pos[v], self.pos[u])
+        };
+        let start = if include_lca { lo } else { lo + 1 };
+        if start <= hi {
+            ranges.push(start..hi + 1);
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::segment_tree::SegmentTree;
+    use crate::graph::tree::random_tree;
+    use crate::util::rand::XorShift64;
+
+    /// Vertices of the path between `u` and `v` by walking up to the lca.
+    fn walk(tree: &Tree, mut u: usize, mut v: usize) -> Vec<usize> {
+        let mut path = vec![];
+        while u != v {
+            if tree.depth(u) < tree.depth(v) {
+                std::mem::swap(&mut u, &mut v);
+            }
+            path.push(u);
+            u = tree.parent(u).unwrap();
+        }
+        path.push(u);
+        path
+    }
+
+    #[test]
+    fn small() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \   \
+        // 3   4   5
+        let tree = Tree::from_edges(6, &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)], 0);
+        let hld = Hld::new(&tree);
+        assert_eq!(hld.lca(3, 5), 0);
+        assert_eq!(hld.lca(4, 3), 1);
+        assert_eq!(hld.lca(2, 5), 2);
+        assert_eq!(hld.subtree_range(0), 0..6);
+        assert_eq!(hld.subtree_range(1).len(), 3);
+
+        let total = |ranges: Vec<Range<usize>>| ranges.into_iter().map(|r| r.len()).sum::<usize>();
+        assert_eq!(total(hld.path_ranges(3, 5)), 5);
+        assert_eq!(total(hld.edge_path_ranges(3, 5)), 4);
+        assert_eq!(total(hld.path_ranges(4, 4)), 1);
+        assert_eq!(total(hld.edge_path_ranges(4, 4)), 0);
+    }
+
+    #[test]
+    fn path_sum_against_walk() {
+        let mut rng = XorShift64::new(36);
+        let n = 1000;
+        let edges = random_tree(&mut rng, n);
+        let tree = Tree::from_edges(n, &edges, rng.gen_index(n));
+        let hld = Hld::new(&tree);
+        let mut positions = (0..n).map(|v| hld.pos(v)).collect::<Vec<_>>();
+        positions.sort_unstable();
+        assert_eq!(positions, (0..n).collect::<Vec<_>>());
+
+        let mut value = vec![0i64; n];
+        let mut seg = SegmentTree::new(n, 0i64, |a, b| a + b);
+        for _ in 0..2000 {
+            let v = rng.gen_index(n);
+            value[v] = rng.gen_range(0..1000) as i64;
+            seg.update(hld.pos(v), value[v]);
+
+            let (a, b) = (rng.gen_index(n), rng.gen_index(n));
+            let path = walk(&tree, a, b);
+            let lca = tree.lca(a, b);
+            assert_eq!(hld.lca(a, b), lca);
+
+            let sum = |ranges: Vec<Range<usize>>| {
+                assert!(ranges.len() <= 2 * 11);
+                ranges.into_iter().map(|r| seg.query(r)).sum::<i64>()
+            };
+            let brute = path.iter().map(|&u| value[u]).sum::<i64>();
+            assert_eq!(sum(hld.path_ranges(a, b)), brute);
+            // the value of a vertex stands for the edge to its parent
+            assert_eq!(sum(hld.edge_path_ranges(a, b)), brute - value[lca]);
+        }
+    }
+
+    #[test]
+    fn deep_path() {
+        let n = 200_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let hld = Hld::new(&Tree::from_edges(n, &edges, 0));
+        assert_eq!(hld.path_ranges(n - 1, 0), vec![0..n]);
+        assert_eq!(hld.lca(n - 1, n / 2), n / 2);
+    }
+}
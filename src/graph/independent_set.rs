@@ -0,0 +1,167 @@
+//! Maximum independent set and maximum clique on at most 40 or so vertices.
+//!
+//! Graphs are given as adjacency bitmasks, `adj[v] >> u & 1 == 1` iff `u` and `v` are adjacent.
+//! Meet in the middle over the two halves of the vertices, `O(2^(n/2))` time and memory.
+
+/// Size and vertex mask of a maximum independent set.
+pub fn max_independent_set(n: usize, adj: &[u64]) -> (usize, u64) {
+    let (size, mask) = max_weight_independent_set(n, adj, &vec![1; n]);
+    (size as usize, mask)
+}
+
+/// Size and vertex mask of a maximum clique.
+pub fn max_clique(n: usize, adj: &[u64]) -> (usize, u64) {
+    let all = full(n);
+    let complement = (0..n)
+        .map(|v| !adj[v] & all & !(1 << v))
+        .collect::<Vec<_>>();
+    max_independent_set(n, &complement)
+}
+
+/// Total weight and vertex mask of an independent set maximizing the weight.
+///
+/// Vertices of negative weight are never taken.
+pub fn max_weight_independent_set(n: usize, adj: &[u64], weights: &[i64]) -> (i64, u64) {
+    assert!(n <= 64);
+    assert_eq!(adj.len(), n);
+    assert_eq!(weights.len(), n);
+    let lo = n / 2;
+    let hi = n - lo;
+
+    // best[mask]: best independent subset of the upper half within mask, shifted down by lo
+    let mut best = vec![(0i64, 0u64); 1 << hi];
+    for mask in 1..1usize << hi {
+        let v = mask.trailing_zeros() as usize;
+        let without = best[mask & (mask - 1)];
+        let rest = mask & !(1 << v) & !((adj[lo + v] >> lo) as usize);
+        let (w, m) = best[rest];
+        let with = (w + weights[lo + v], m | 1 << v);
+        best[mask] = if with.0 > without.0 { with } else { without };
+    }
+
+    // sets of the lower half, with the upper half vertices they exclude
+    let mut independent = vec![true; 1 << lo];
+    let mut weight = vec![0i64; 1 << lo];
+    let mut excluded = vec![0u64; 1 << lo];
+    let mut answer = best[(1 << hi) - 1];
+    answer.1 <<= lo;
+    for mask in 1..1usize << lo {
+        let v = mask.trailing_zeros() as usize;
+        let prev = mask & (mask - 1);
+        independent[mask] = independent[prev] && adj[v] & prev as u64 == 0;
+        if !independent[mask] {
+            continue;
+        }
+        weight[mask] = weight[prev] + weights[v];
+        excluded[mask] = excluded[prev] | adj[v] >> lo;
+        let (w, m) = best[((1 << hi) - 1) & !excluded[mask] as usize];
+        if weight[mask] + w > answer.0 {
+            answer = (weight[mask] + w, mask as u64 | m << lo);
+        }
+    }
+    answer
+}
+
+fn full(n: usize) -> u64 {
+    if n == 64 {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MaxFlow;
+    use crate::util::rand::XorShift64;
+
+    fn random_graph(rng: &mut XorShift64, n: usize, density: u64) -> Vec<u64> {
+        let mut adj = vec![0; n];
+        for u in 0..n {
+            for v in u + 1..n {
+                if rng.gen_range(0..100) < density {
+                    adj[u] |= 1 << v;
+                    adj[v] |= 1 << u;
+                }
+            }
+        }
+        adj
+    }
+
+    fn is_independent(adj: &[u64], mask: u64) -> bool {
+        (0..adj.len()).all(|v| mask >> v & 1 == 0 || adj[v] & mask == 0)
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let mut rng = XorShift64::new(12);
+        for _ in 0..100 {
+            let n = rng.gen_index(21);
+            let adj = random_graph(&mut rng, n, 40);
+            let weights = (0..n)
+                .map(|_| rng.gen_range(0..21) as i64 - 5)
+                .collect::<Vec<_>>();
+            let (mut size, mut weight) = (0, 0);
+            for mask in 0..1u64 << n {
+                if is_independent(&adj, mask) {
+                    size = size.max(mask.count_ones() as usize);
+                    let w = (0..n)
+                        .filter(|&v| mask >> v & 1 == 1)
+                        .map(|v| weights[v])
+                        .sum();
+                    weight = weight.max(w);
+                }
+            }
+
+            let (s, mask) = max_independent_set(n, &adj);
+            assert_eq!(s, size);
+            assert_eq!(mask.count_ones() as usize, size);
+            assert!(is_independent(&adj, mask));
+
+            let (w, mask) = max_weight_independent_set(n, &adj, &weights);
+            assert_eq!(w, weight);
+            assert!(is_independent(&adj, mask));
+
+            let (c, mask) = max_clique(n, &adj);
+            let complement = (0..n)
+                .map(|v| !adj[v] & full(n) & !(1 << v))
+                .collect::<Vec<_>>();
+            assert_eq!(c, max_independent_set(n, &complement).0);
+            assert!((0..n).all(|v| mask >> v & 1 == 0 || (adj[v] | 1 << v) & mask == mask));
+        }
+    }
+
+    #[test]
+    fn bipartite_is_n_minus_matching() {
+        let mut rng = XorShift64::new(13);
+        for _ in 0..20 {
+            // left 0..10, right 10..20
+            let n = 20;
+            let mut adj = vec![0u64; n];
+            let mut flow = MaxFlow::new(n + 2);
+            for u in 0..10 {
+                flow.add_edge(n, u, 1);
+                flow.add_edge(10 + u, n + 1, 1);
+                for v in 10..20 {
+                    if rng.gen_index(4) == 0 {
+                        adj[u] |= 1 << v;
+                        adj[v] |= 1 << u;
+                        flow.add_edge(u, v, 1);
+                    }
+                }
+            }
+            let matching = flow.flow(n, n + 1) as usize;
+            assert_eq!(max_independent_set(n, &adj).0, n - matching);
+        }
+    }
+
+    #[test]
+    fn forty_vertices() {
+        let mut rng = XorShift64::new(14);
+        let adj = random_graph(&mut rng, 40, 10);
+        let (size, mask) = max_independent_set(40, &adj);
+        assert!(is_independent(&adj, mask));
+        assert_eq!(mask.count_ones() as usize, size);
+    }
+}
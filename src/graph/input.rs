@@ -0,0 +1,117 @@
+//! Read edge lists straight into graphs.
+//!
+//! Kept out of `cio` so that the scanner doesn't depend on the graph types.
+
+use std::io::BufRead;
+
+use super::dag::Dag;
+use super::undirected::Graph;
+use crate::contest::cio::Scanner;
+
+/// Read `m` lines of `u v w` into a directed graph of `n` vertices.
+pub fn scan_dag<R: BufRead>(
+    scanner: &mut Scanner<R>,
+    n: usize,
+    m: usize,
+    one_indexed: bool,
+) -> Dag {
+    let offset = one_indexed as usize;
+    let mut g = Dag::new(n);
+    for _ in 0..m {
+        let (u, v, w) = scanner.scan::<(usize, usize, i64)>();
+        g.add_edge(u - offset, v - offset, w);
+    }
+    g
+}
+
+/// Read `m` lines of `u v` into a directed graph of `n` vertices with unit costs.
+pub fn scan_dag_unweighted<R: BufRead>(
+    scanner: &mut Scanner<R>,
+    n: usize,
+    m: usize,
+    one_indexed: bool,
+) -> Dag {
+    let offset = one_indexed as usize;
+    let mut g = Dag::new(n);
+    for _ in 0..m {
+        let (u, v) = scanner.scan::<(usize, usize)>();
+        g.add_edge(u - offset, v - offset, 1);
+    }
+    g
+}
+
+/// Read `m` lines of `u v w` into an undirected graph of `n` vertices.
+pub fn scan_graph<R: BufRead>(
+    scanner: &mut Scanner<R>,
+    n: usize,
+    m: usize,
+    one_indexed: bool,
+) -> Graph {
+    let offset = one_indexed as usize;
+    let mut g = Graph::new(n);
+    for _ in 0..m {
+        let (u, v, w) = scanner.scan::<(usize, usize, i64)>();
+        g.add_edge(u - offset, v - offset, w);
+    }
+    g
+}
+
+/// Read `m` lines of `u v` into an undirected graph of `n` vertices with unit costs.
+pub fn scan_graph_unweighted<R: BufRead>(
+    scanner: &mut Scanner<R>,
+    n: usize,
+    m: usize,
+    one_indexed: bool,
+) -> Graph {
+    let offset = one_indexed as usize;
+    let mut g = Graph::new(n);
+    for _ in 0..m {
+        let (u, v) = scanner.scan::<(usize, usize)>();
+        g.add_edge(u - offset, v - offset, 1);
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adjacency::dijkstra_all;
+
+    #[test]
+    fn dag() {
+        let input = "4 5\n1 2 5\n1 3 2\n2 4 4\n3 2 2\n3 4 3\n";
+        let mut scanner = Scanner::from(input);
+        let (n, m) = scanner.scan::<(usize, usize)>();
+        let g = scan_dag(&mut scanner, n, m, true);
+        let sp = g.floyd_warshall();
+        assert_eq!(sp.get(0, 3), Some(5));
+        assert_eq!(sp.get(0, 1), Some(4));
+        assert_eq!(sp.get(3, 0), None);
+
+        let input = "3 2\n0 1\n1 2\n";
+        let mut scanner = Scanner::from(input);
+        let (n, m) = scanner.scan::<(usize, usize)>();
+        let g = scan_dag_unweighted(&mut scanner, n, m, false);
+        assert_eq!(g.dijkstra(0, 2), Some(2));
+        assert_eq!(g.dijkstra(2, 0), None);
+    }
+
+    #[test]
+    fn graph() {
+        let input = "4 4\n1 2 5\n2 3 1\n3 4 1\n1 4 9\n";
+        let mut scanner = Scanner::from(input);
+        let (n, m) = scanner.scan::<(usize, usize)>();
+        let g = scan_graph(&mut scanner, n, m, true);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(
+            dijkstra_all(&g, 3),
+            vec![Some(7), Some(2), Some(1), Some(0)]
+        );
+
+        let input = "4 2\n0 1\n2 1\n";
+        let mut scanner = Scanner::from(input);
+        let (n, m) = scanner.scan::<(usize, usize)>();
+        let g = scan_graph_unweighted(&mut scanner, n, m, false);
+        assert_eq!(g.bfs(2), vec![Some(2), Some(1), Some(0), None]);
+    }
+}
@@ -0,0 +1,221 @@
+use super::undirected::Graph;
+
+const NONE: usize = usize::MAX;
+
+/// Bridges and articulation points of an undirected [`Graph`] by lowlink.
+///
+/// The dfs skips the edge to the parent by its id rather than the parent vertex,
+/// so one of two parallel edges is never a bridge.
+pub struct LowLink {
+    ord: Vec<usize>,
+    low: Vec<usize>,
+    bridge: Vec<bool>,
+    bridges: Vec<(usize, usize)>,
+    articulation: Vec<bool>,
+}
+
+impl LowLink {
+    pub fn new(g: &Graph) -> Self {
+        let n = g.len();
+        let mut ord = vec![NONE; n];
+        let mut low = vec![NONE; n];
+        let mut bridge = vec![false; g.edge_count()];
+        let mut bridges = vec![];
+        let mut articulation = vec![false; n];
+        let mut now = 0;
+        // (vertex, edge id from the parent, index of the next incident edge)
+        let mut stack: Vec<(usize, usize, usize)> = vec![];
+
+        for root in 0..n {
+            if ord[root] != NONE {
+                continue;
+            }
+            ord[root] = now;
+            low[root] = now;
+            now += 1;
+            stack.push((root, NONE, 0));
+            let mut root_children = 0;
+
+            while let Some(&mut (v, parent_edge, ref mut i)) = stack.last_mut() {
+                if let Some(&(u, id)) = g.incident(v).get(*i) {
+                    *i += 1;
+                    if id == parent_edge {
+                        continue;
+                    }
+                    if ord[u] == NONE {
+                        ord[u] = now;
+                        low[u] = now;
+                        now += 1;
+                        stack.push((u, id, 0));
+                        if v == root {
+                            root_children += 1;
+                        }
+                    } else {
+                        low[v] = low[v].min(ord[u]);
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                let Some(&(p, _, _)) = stack.last() else { break };
+                low[p] = low[p].min(low[v]);
+                if low[v] > ord[p] {
+                    bridge[parent_edge] = true;
+                    bridges.push((p.min(v), p.max(v)));
+                }
+                if p != root && low[v] >= ord[p] {
+                    articulation[p] = true;
+                }
+            }
+            articulation[root] = root_children >= 2;
+        }
+
+        bridges.sort_unstable();
+        Self {
+            ord,
+            low,
+            bridge,
+            bridges,
+            articulation,
+        }
+    }
+
+    /// Bridges as `(u, v)` with `u < v` in ascending order.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.bridges.clone()
+    }
+
+    /// Whether the edge `id` of the graph is a bridge.
+    pub fn is_bridge(&self, id: usize) -> bool {
+        self.bridge[id]
+    }
+
+    /// Articulation points in ascending order.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        (0..self.articulation.len())
+            .filter(|&v| self.articulation[v])
+            .collect()
+    }
+
+    /// Preorder of `v` in the dfs.
+    pub fn ord(&self, v: usize) -> usize {
+        self.ord[v]
+    }
+
+    /// Smallest `ord` reachable from the dfs subtree of `v` with one back edge.
+    pub fn low(&self, v: usize) -> usize {
+        self.low[v]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::unionfind::UnionFind;
+    use crate::util::rand::XorShift64;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut g = Graph::new(n);
+        for &(u, v) in edges {
+            g.add_edge(u, v, 1);
+        }
+        g
+    }
+
+    #[test]
+    fn path() {
+        let g = graph(4, &[(0, 1), (2, 1), (2, 3)]);
+        let ll = LowLink::new(&g);
+        assert_eq!(ll.bridges(), vec![(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(ll.articulation_points(), vec![1, 2]);
+        assert!((0..3).all(|id| ll.is_bridge(id)));
+    }
+
+    #[test]
+    fn cycle() {
+        let g = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+        let ll = LowLink::new(&g);
+        assert!(ll.bridges().is_empty());
+        assert!(ll.articulation_points().is_empty());
+    }
+
+    #[test]
+    fn parallel_edge() {
+        // 0 = 1 - 2, with a self loop on 2
+        let g = graph(3, &[(0, 1), (1, 0), (1, 2), (2, 2)]);
+        let ll = LowLink::new(&g);
+        assert_eq!(ll.bridges(), vec![(1, 2)]);
+        assert!(ll.is_bridge(2) && !ll.is_bridge(0) && !ll.is_bridge(1));
+        assert_eq!(ll.articulation_points(), vec![1]);
+    }
+
+    #[test]
+    fn two_cycles_sharing_a_vertex() {
+        // triangles 0 1 2 and 2 3 4, plus 5 hanging off 4 and isolated 6
+        let g = graph(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (4, 5)]);
+        let ll = LowLink::new(&g);
+        assert_eq!(ll.bridges(), vec![(4, 5)]);
+        assert_eq!(ll.articulation_points(), vec![2, 4]);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        fn components(
+            n: usize,
+            edges: &[(usize, usize)],
+            skip_edge: usize,
+            skip_vertex: usize,
+        ) -> usize {
+            let mut uf = UnionFind::new(n);
+            for (i, &(u, v)) in edges.iter().enumerate() {
+                if i != skip_edge && u != skip_vertex && v != skip_vertex {
+                    uf.union(u, v);
+                }
+            }
+            (0..n)
+                .filter(|&v| {
+                    v != skip_vertex && (0..v).all(|u| u == skip_vertex || !uf.equiv(u, v))
+                })
+                .count()
+        }
+
+        let mut rng = XorShift64::new(24);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_index(10);
+            let edges = (0..rng.gen_index(2 * n))
+                .map(|_| (rng.gen_index(n), rng.gen_index(n)))
+                .collect::<Vec<_>>();
+            let ll = LowLink::new(&graph(n, &edges));
+            let base = components(n, &edges, NONE, NONE);
+
+            for i in 0..edges.len() {
+                assert_eq!(ll.is_bridge(i), components(n, &edges, i, NONE) > base);
+            }
+            for v in 0..n {
+                let isolated = edges.iter().all(|&(a, b)| a == b || (a != v && b != v));
+                let articulation = components(n, &edges, NONE, v) > base - isolated as usize;
+                assert_eq!(ll.articulation_points().contains(&v), articulation, "{}", v);
+            }
+
+            // merging over non-bridges leaves bridges between different 2-edge-connected components
+            let mut uf = UnionFind::new(n);
+            for (i, &(u, v)) in edges.iter().enumerate() {
+                if !ll.is_bridge(i) {
+                    uf.union(u, v);
+                }
+            }
+            for (u, v) in ll.bridges() {
+                assert!(!uf.equiv(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 200_000;
+        let g = graph(n, &(1..n).map(|v| (v - 1, v)).collect::<Vec<_>>());
+        let ll = LowLink::new(&g);
+        assert_eq!(ll.bridges().len(), n - 1);
+        assert_eq!(ll.articulation_points().len(), n - 2);
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::VecDeque;
+
+/// Dinic's maximum flow.
+pub struct MaxFlow {
+    graph: Vec<Vec<usize>>,
+    // edge `e` and its reverse `e ^ 1`
+    edges: Vec<FlowEdge>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct FlowEdge {
+    to: usize,
+    cap: i64,
+}
+
+impl MaxFlow {
+    pub fn new(n: usize) -> Self {
+        Self {
+            graph: vec![vec![]; n],
+            edges: vec![],
+        }
+    }
+
+    /// Add an edge of capacity `cap` and return its id, the `i`-th added edge has id `i`.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64) -> usize {
+        assert!(cap >= 0, "negative capacity");
+        let e = self.edges.len();
+        self.graph[from].push(e);
+        self.edges.push(FlowEdge { to, cap });
+        self.graph[to].push(e + 1);
+        self.edges.push(FlowEdge { to: from, cap: 0 });
+        e / 2
+    }
+
+    /// `(from, to, cap, flow)` of the edge `id`, the flow is what currently passes through it.
+    pub fn get_edge(&self, id: usize) -> (usize, usize, i64, i64) {
+        let (e, rev) = (self.edges[2 * id], self.edges[2 * id + 1]);
+        (rev.to, e.to, e.cap + rev.cap, rev.cap)
+    }
+
+    /// [`MaxFlow::get_edge`] of every edge in insertion order.
+    pub fn edges(&self) -> Vec<(usize, usize, i64, i64)> {
+        (0..self.edges.len() / 2)
+            .map(|id| self.get_edge(id))
+            .collect()
+    }
+
+    /// Push as much flow as possible from `s` to `t` and return the amount.
+    pub fn flow(&mut self, s: usize, t: usize) -> i64 {
+        assert_ne!(s, t);
+        let n = self.graph.len();
+        let mut total = 0;
+        loop {
+            let level = self.levels(s);
+            if level[t] == usize::MAX {
+                return total;
+            }
+            let mut iter = vec![0; n];
+            loop {
+                let f = self.augment(s, t, i64::MAX, &level, &mut iter);
+                if f == 0 {
+                    break;
+                }
+                total += f;
+            }
+        }
+    }
+
+    /// Vertices reachable from `s` in the residual graph, the source side of a minimum cut after [`MaxFlow::flow`].
+    pub fn min_cut(&self, s: usize) -> Vec<bool> {
+        let level = self.levels(s);
+        level.into_iter().map(|l| l != usize::MAX).collect()
+    }
+
+    /// BFS distance from `s` over edges with remaining capacity.
+    fn levels(&self, s: usize) -> Vec<usize> {
+        let mut level = vec![usize::MAX; self.graph.len()];
+        level[s] = 0;
+        let mut queue = VecDeque::from([s]);
+        while let Some(v) = queue.pop_front() {
+            for &e in &self.graph[v] {
+                let FlowEdge { to, cap } = self.edges[e];
+                if cap > 0 && level[to] == usize::MAX {
+                    level[to] = level[v] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+        level
+    }
+
+    fn augment(
+        &mut self,
+        v: usize,
+        t: usize,
+        limit: i64,
+        level: &[usize],
+        iter: &mut [usize],
+    ) -> i64 {
+        if v == t {
+            return limit;
+        }
+        while let Some(&e) = self.graph[v].get(iter[v]) {
+            let FlowEdge { to, cap } = self.edges[e];
+            if cap > 0 && level[to] == level[v] + 1 {
+                let f = self.augment(to, t, limit.min(cap), level, iter);
+                if f > 0 {
+                    self.edges[e].cap -= f;
+                    self.edges[e ^ 1].cap += f;
+                    return f;
+                }
+            }
+            iter[v] += 1;
+        }
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_cut_equals_flow() {
+        // CLRS figure 26.1, maximum flow 23
+        let mut g = MaxFlow::new(6);
+        for (from, to, cap) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ] {
+            g.add_edge(from, to, cap);
+        }
+        assert_eq!(g.flow(0, 5), 23);
+
+        let cut = g.min_cut(0);
+        assert!(cut[0] && !cut[5]);
+        let capacity = g
+            .edges()
+            .into_iter()
+            .filter(|&(from, to, _, _)| cut[from] && !cut[to])
+            .map(|(_, _, cap, _)| cap)
+            .sum::<i64>();
+        assert_eq!(capacity, 23);
+        // edges across the cut are saturated
+        for (from, to, cap, flow) in g.edges() {
+            if cut[from] && !cut[to] {
+                assert_eq!(cap, flow);
+            }
+        }
+
+        // nothing more to push
+        assert_eq!(g.flow(0, 5), 0);
+    }
+
+    #[test]
+    fn bipartite_matching() {
+        // left 0..3, right 3..6, source 6, sink 7
+        let mut g = MaxFlow::new(8);
+        let pairs = [(0, 3), (0, 4), (1, 3), (2, 3)];
+        let ids = pairs
+            .iter()
+            .map(|&(l, r)| g.add_edge(l, r, 1))
+            .collect::<Vec<_>>();
+        for v in 0..3 {
+            g.add_edge(6, v, 1);
+            g.add_edge(v + 3, 7, 1);
+        }
+        assert_eq!(g.flow(6, 7), 2);
+
+        let matched = ids
+            .into_iter()
+            .map(|id| g.get_edge(id))
+            .filter(|&(_, _, _, flow)| flow == 1)
+            .map(|(l, r, _, _)| (l, r))
+            .collect::<Vec<_>>();
+        assert_eq!(matched.len(), 2);
+        assert!(matched.contains(&(0, 4)));
+    }
+
+    #[test]
+    fn residual_edges() {
+        let mut g = MaxFlow::new(3);
+        let a = g.add_edge(0, 1, 1_000_000_000_000_000_000);
+        let b = g.add_edge(1, 2, 999_999_999_999_999_999);
+        let c = g.add_edge(0, 2, 5);
+        assert_eq!((a, b, c), (0, 1, 2));
+        assert_eq!(g.get_edge(a), (0, 1, 1_000_000_000_000_000_000, 0));
+
+        assert_eq!(g.flow(0, 2), 1_000_000_000_000_000_004);
+        assert_eq!(
+            g.get_edge(a),
+            (0, 1, 1_000_000_000_000_000_000, 999_999_999_999_999_999)
+        );
+        assert_eq!(g.get_edge(c), (0, 2, 5, 5));
+        assert_eq!(g.min_cut(0), vec![true, true, false]);
+    }
+}
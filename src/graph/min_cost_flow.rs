@@ -0,0 +1,206 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Minimum cost flow by successive shortest paths, Dijkstra with potentials.
+///
+/// Edge costs must not be negative, residual edges of negative cost are handled by the potentials.
+/// Like AC Library, [`MinCostFlow::flow`] or [`MinCostFlow::slope`] is meant to be called once.
+pub struct MinCostFlow {
+    graph: Vec<Vec<usize>>,
+    // edge `e` and its reverse `e ^ 1`
+    edges: Vec<CostEdge>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CostEdge {
+    to: usize,
+    cap: i64,
+    cost: i64,
+}
+
+impl MinCostFlow {
+    pub fn new(n: usize) -> Self {
+        Self {
+            graph: vec![vec![]; n],
+            edges: vec![],
+        }
+    }
+
+    /// Add an edge of capacity `cap` costing `cost` per unit and return its id,
+    /// the `i`-th added edge has id `i`.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        assert!(cap >= 0, "negative capacity");
+        assert!(cost >= 0, "negative cost");
+        let e = self.edges.len();
+        self.graph[from].push(e);
+        self.edges.push(CostEdge { to, cap, cost });
+        self.graph[to].push(e + 1);
+        self.edges.push(CostEdge {
+            to: from,
+            cap: 0,
+            cost: -cost,
+        });
+        e / 2
+    }
+
+    /// `(from, to, cap, flow, cost)` of the edge `id`.
+    pub fn get_edge(&self, id: usize) -> (usize, usize, i64, i64, i64) {
+        let (e, rev) = (self.edges[2 * id], self.edges[2 * id + 1]);
+        (rev.to, e.to, e.cap + rev.cap, rev.cap, e.cost)
+    }
+
+    /// Push up to `flow_limit` from `s` to `t` as cheap as possible, `(flow, cost)`.
+    pub fn flow(&mut self, s: usize, t: usize, flow_limit: i64) -> (i64, i64) {
+        *self.slope(s, t, flow_limit).last().unwrap()
+    }
+
+    /// Breakpoints `(flow, cost)` of the minimum cost as a function of the flow,
+    /// starting at `(0, 0)` and ending at the result of [`MinCostFlow::flow`].
+    ///
+    /// The cost is linear between consecutive breakpoints and the slopes strictly increase.
+    pub fn slope(&mut self, s: usize, t: usize, flow_limit: i64) -> Vec<(i64, i64)> {
+        assert_ne!(s, t);
+        let n = self.graph.len();
+        let mut dual = vec![0; n];
+        let mut prev = vec![usize::MAX; n];
+        let (mut flow, mut cost) = (0, 0);
+        let mut prev_cost_per_flow = None;
+        let mut result = vec![(0, 0)];
+
+        while flow < flow_limit {
+            if !self.refine_dual(s, t, &mut dual, &mut prev) {
+                break;
+            }
+            let mut c = flow_limit - flow;
+            let mut v = t;
+            while v != s {
+                let e = prev[v];
+                c = c.min(self.edges[e].cap);
+                v = self.edges[e ^ 1].to;
+            }
+            let mut v = t;
+            while v != s {
+                let e = prev[v];
+                self.edges[e].cap -= c;
+                self.edges[e ^ 1].cap += c;
+                v = self.edges[e ^ 1].to;
+            }
+
+            let d = -dual[s];
+            flow += c;
+            cost += c * d;
+            if prev_cost_per_flow == Some(d) {
+                result.pop();
+            }
+            result.push((flow, cost));
+            prev_cost_per_flow = Some(d);
+        }
+        result
+    }
+
+    /// Dijkstra over reduced costs, then move the potentials so they stay valid.
+    /// Returns `false` if `t` is unreachable.
+    fn refine_dual(&self, s: usize, t: usize, dual: &mut [i64], prev: &mut [usize]) -> bool {
+        let n = self.graph.len();
+        let mut dist = vec![i64::MAX; n];
+        let mut visited = vec![false; n];
+        dist[s] = 0;
+        let mut queue = BinaryHeap::from([Reverse((0, s))]);
+
+        while let Some(Reverse((_, v))) = queue.pop() {
+            if visited[v] {
+                continue;
+            }
+            visited[v] = true;
+            if v == t {
+                break;
+            }
+            for &e in &self.graph[v] {
+                let CostEdge { to, cap, cost } = self.edges[e];
+                if visited[to] || cap == 0 {
+                    continue;
+                }
+                // non-negative thanks to the potentials
+                let reduced = cost - dual[to] + dual[v];
+                if dist[to] - dist[v] > reduced {
+                    dist[to] = dist[v] + reduced;
+                    prev[to] = e;
+                    queue.push(Reverse((dist[to], to)));
+                }
+            }
+        }
+
+        if !visited[t] {
+            return false;
+        }
+        for v in 0..n {
+            if visited[v] {
+                dual[v] -= dist[t] - dist[v];
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assignment() {
+        // workers 0..3, jobs 3..6, source 6, sink 7
+        let cost = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        let mut g = MinCostFlow::new(8);
+        let mut ids = vec![];
+        for (i, row) in cost.iter().enumerate() {
+            g.add_edge(6, i, 1, 0);
+            g.add_edge(3 + i, 7, 1, 0);
+            for (j, &c) in row.iter().enumerate() {
+                ids.push(g.add_edge(i, 3 + j, 1, c));
+            }
+        }
+        assert_eq!(g.flow(6, 7, 3), (3, 5));
+
+        let assigned = ids
+            .into_iter()
+            .map(|id| g.get_edge(id))
+            .filter(|&(_, _, _, flow, _)| flow == 1)
+            .map(|(i, j, _, _, _)| (i, j - 3))
+            .collect::<Vec<_>>();
+        assert_eq!(assigned, vec![(0, 1), (1, 0), (2, 2)]);
+    }
+
+    fn network() -> MinCostFlow {
+        // 0 -> 1 -> 3 costs 2 for two units, 0 -> 2 -> 3 costs 5 for one
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 2, 1);
+        g.add_edge(1, 3, 2, 1);
+        g.add_edge(0, 2, 1, 5);
+        g.add_edge(2, 3, 1, 0);
+        g
+    }
+
+    #[test]
+    fn slope() {
+        assert_eq!(network().slope(0, 3, 10), vec![(0, 0), (2, 4), (3, 9)]);
+        assert_eq!(network().flow(0, 3, 10), (3, 9));
+        assert_eq!(network().slope(3, 0, 10), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn partial_flow() {
+        assert_eq!(network().flow(0, 3, 1), (1, 2));
+        assert_eq!(network().slope(0, 3, 3), vec![(0, 0), (2, 4), (3, 9)]);
+
+        // the second path cancels the flow on 1 -> 2 through its residual edge of negative cost
+        //   0 -> 1 -> 3 and 0 -> 2 -> 3 with a shortcut 1 -> 2
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 1, 1);
+        g.add_edge(0, 2, 1, 10);
+        g.add_edge(1, 2, 1, 1);
+        g.add_edge(1, 3, 1, 10);
+        g.add_edge(2, 3, 1, 1);
+        assert_eq!(g.slope(0, 3, 5), vec![(0, 0), (1, 3), (2, 22)]);
+        assert_eq!(g.get_edge(2), (1, 2, 1, 0, 1));
+    }
+}
@@ -1 +1,40 @@
+pub mod adjacency;
+pub mod bipartite_matching;
+pub mod centroid;
+pub mod cost;
+pub mod csr;
 pub mod dag;
+pub mod doubling;
+pub mod eulerian;
+pub mod grid;
+pub mod hld;
+pub mod independent_set;
+pub mod input;
+pub mod lowlink;
+pub mod max_flow;
+pub mod min_cost_flow;
+pub mod mst;
+pub mod project_selection;
+pub mod rerooting;
+pub mod scc;
+pub mod tree;
+pub mod undirected;
+
+pub use adjacency::Adjacency;
+pub use bipartite_matching::BipartiteMatching;
+pub use centroid::CentroidDecomposition;
+pub use csr::Csr;
+pub use dag::{Dag, EdgeId};
+pub use doubling::{Doubling, DoublingFold};
+pub use eulerian::eulerian_path;
+pub use hld::Hld;
+pub use input::{scan_dag, scan_dag_unweighted, scan_graph, scan_graph_unweighted};
+pub use lowlink::LowLink;
+pub use max_flow::MaxFlow;
+pub use min_cost_flow::MinCostFlow;
+pub use mst::{minimum_spanning_tree, prim, prim_dense};
+pub use project_selection::ProjectSelection;
+pub use rerooting::Rerooting;
+pub use scc::{scc, Scc};
+pub use tree::{tree_diameter, EulerTour, Tree};
+pub use undirected::{bipartite_coloring, Graph};
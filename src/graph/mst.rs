@@ -0,0 +1,203 @@
+//! Minimum spanning trees of undirected graphs.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::undirected::Graph;
+use crate::collections::unionfind::{UnionFind, UnionResult};
+
+/// Kruskal's algorithm, total weight and indices into `edges` of a minimum spanning tree.
+///
+/// `None` if the graph is disconnected. Ties are broken by the order of `edges`,
+/// so the chosen indices are deterministic and ascending among equal weights.
+pub fn minimum_spanning_tree(n: usize, edges: &[(usize, usize, i64)]) -> Option<(i64, Vec<usize>)> {
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    // stable, equal weights keep the input order
+    order.sort_by_key(|&i| edges[i].2);
+
+    let mut uf = UnionFind::new(n);
+    let mut total = 0;
+    let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+    for i in order {
+        let (u, v, w) = edges[i];
+        if uf.union(u, v) == UnionResult::Unified {
+            total += w;
+            chosen.push(i);
+        }
+    }
+
+    (chosen.len() + 1 >= n).then_some((total, chosen))
+}
+
+/// Prim's algorithm on a symmetric cost matrix in `O(n^2)`, `i64::MAX` entries are missing edges.
+///
+/// Weight of a minimum spanning tree, `None` if the graph is disconnected.
+pub fn prim_dense(cost: &[Vec<i64>]) -> Option<i64> {
+    let n = cost.len();
+    let mut used = vec![false; n];
+    // cheapest edge from the tree to each vertex
+    let mut min_cost = vec![i64::MAX; n];
+    let mut total = 0;
+    for i in 0..n {
+        let v = (0..n)
+            .filter(|&v| !used[v])
+            .min_by_key(|&v| min_cost[v])
+            .unwrap();
+        if i > 0 {
+            if min_cost[v] == i64::MAX {
+                return None;
+            }
+            total += min_cost[v];
+        }
+        used[v] = true;
+        for u in 0..n {
+            if !used[u] {
+                min_cost[u] = min_cost[u].min(cost[v][u]);
+            }
+        }
+    }
+    Some(total)
+}
+
+/// Prim's algorithm with a binary heap in `O(m log m)`, growing the tree from `start`.
+///
+/// Weight of a minimum spanning tree, `None` if some vertex is unreachable from `start`.
+pub fn prim(g: &Graph, start: usize) -> Option<i64> {
+    let mut used = vec![false; g.len()];
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+    let mut total = 0;
+    let mut count = 0;
+    while let Some(Reverse((cost, v))) = queue.pop() {
+        if used[v] {
+            continue;
+        }
+        used[v] = true;
+        total += cost;
+        count += 1;
+        for (u, c) in g.neighbors(v) {
+            if !used[u] {
+                queue.push(Reverse((c, u)));
+            }
+        }
+    }
+    (count == g.len()).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_spanning_tree(n: usize, edges: &[(usize, usize, i64)], chosen: &[usize]) {
+        assert_eq!(chosen.len(), n - 1);
+        let mut uf = UnionFind::new(n);
+        for &i in chosen {
+            let (u, v, _) = edges[i];
+            assert_eq!(uf.union(u, v), UnionResult::Unified, "cycle at edge {}", i);
+        }
+        assert!((1..n).all(|v| uf.equiv(0, v)));
+    }
+
+    #[test]
+    fn classic() {
+        //   0 --1-- 1
+        //   | \     |
+        //   4   3   2
+        //   |     \ |
+        //   3 --5-- 2
+        let edges = [(0, 1, 1), (1, 2, 2), (0, 2, 3), (0, 3, 4), (2, 3, 5)];
+        let (weight, chosen) = minimum_spanning_tree(4, &edges).unwrap();
+        assert_eq!(weight, 7);
+        assert_eq!(chosen, vec![0, 1, 3]);
+        assert_spanning_tree(4, &edges, &chosen);
+    }
+
+    #[test]
+    fn disconnected() {
+        let edges = [(0, 1, 1), (2, 3, 1)];
+        assert_eq!(minimum_spanning_tree(4, &edges), None);
+        assert_eq!(minimum_spanning_tree(1, &[]), Some((0, vec![])));
+        assert_eq!(minimum_spanning_tree(0, &[]), Some((0, vec![])));
+    }
+
+    #[test]
+    fn duplicate_weights() {
+        // a 4-cycle of equal weights with a parallel edge and a self loop
+        let edges = [
+            (0, 0, 0),
+            (0, 1, 2),
+            (1, 2, 2),
+            (1, 0, 2),
+            (2, 3, 2),
+            (3, 0, 2),
+        ];
+        let (weight, chosen) = minimum_spanning_tree(4, &edges).unwrap();
+        assert_eq!(weight, 6);
+        assert_eq!(chosen, vec![1, 2, 4]);
+        assert_spanning_tree(4, &edges, &chosen);
+    }
+
+    #[test]
+    fn random_spanning() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(21);
+        for _ in 0..50 {
+            let n = 2 + rng.gen_index(30);
+            let mut edges = (1..n)
+                .map(|v| (rng.gen_index(v), v, rng.gen_range(0..10) as i64))
+                .collect::<Vec<_>>();
+            for _ in 0..rng.gen_index(3 * n) {
+                let w = rng.gen_range(0..10) as i64 - 3;
+                edges.push((rng.gen_index(n), rng.gen_index(n), w));
+            }
+            let (weight, chosen) = minimum_spanning_tree(n, &edges).unwrap();
+            assert_spanning_tree(n, &edges, &chosen);
+            assert_eq!(weight, chosen.iter().map(|&i| edges[i].2).sum::<i64>());
+        }
+    }
+
+    #[test]
+    fn prim_dense_matrix() {
+        const X: i64 = i64::MAX;
+        let cost = vec![
+            vec![0, 1, 3, 4],
+            vec![1, 0, 2, X],
+            vec![3, 2, 0, 5],
+            vec![4, X, 5, 0],
+        ];
+        assert_eq!(prim_dense(&cost), Some(7));
+
+        // 2 is unreachable
+        let cost = vec![vec![0, 1, X], vec![1, 0, X], vec![X, X, 0]];
+        assert_eq!(prim_dense(&cost), None);
+        assert_eq!(prim_dense(&[]), Some(0));
+        assert_eq!(prim_dense(&[vec![0]]), Some(0));
+    }
+
+    #[test]
+    fn prim_matches_kruskal() {
+        use crate::util::rand::XorShift64;
+
+        let mut rng = XorShift64::new(22);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(20);
+            let edges = (0..rng.gen_index(4 * n))
+                .map(|_| {
+                    let w = rng.gen_range(0..30) as i64 - 10;
+                    (rng.gen_index(n), rng.gen_index(n), w)
+                })
+                .collect::<Vec<_>>();
+            let kruskal = minimum_spanning_tree(n, &edges).map(|(w, _)| w);
+
+            let mut g = Graph::new(n);
+            let mut cost = vec![vec![i64::MAX; n]; n];
+            for &(u, v, w) in &edges {
+                g.add_edge(u, v, w);
+                cost[u][v] = cost[u][v].min(w);
+                cost[v][u] = cost[v][u].min(w);
+            }
+            assert_eq!(prim(&g, rng.gen_index(n)), kruskal);
+            assert_eq!(prim_dense(&cost), kruskal);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Project selection problems solved as a minimum cut.
+//!
+//! A taken item is on the source side of the cut, each cost becomes an edge
+//! which is cut exactly when the cost applies.
+
+use super::max_flow::MaxFlow;
+
+pub struct ProjectSelection {
+    n: usize,
+    base: i64,
+    // (from, to, cap) over items, source `n` and sink `n + 1`
+    edges: Vec<(usize, usize, i64)>,
+    chosen: Option<Vec<bool>>,
+}
+
+impl ProjectSelection {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            base: 0,
+            edges: vec![],
+            chosen: None,
+        }
+    }
+
+    /// Earn `profit` if item `i` is taken, a negative profit is a penalty.
+    pub fn gain(&mut self, i: usize, profit: i64) {
+        if profit < 0 {
+            return self.penalty(i, -profit);
+        }
+        // earn it up front, pay it back unless taken
+        self.base += profit;
+        self.edges.push((self.n, i, profit));
+    }
+
+    /// Pay `cost` if item `i` is taken, a negative cost is a gain.
+    pub fn penalty(&mut self, i: usize, cost: i64) {
+        if cost < 0 {
+            return self.gain(i, -cost);
+        }
+        self.edges.push((i, self.n + 1, cost));
+    }
+
+    /// Pay `cost` if item `i` is taken but `j` is not, an infinite cost makes `i` require `j`.
+    pub fn must_pair_penalty(&mut self, i: usize, j: usize, cost: i64) {
+        assert!(cost >= 0, "pair penalty must not be negative");
+        self.edges.push((i, j, cost));
+    }
+
+    /// Maximum net profit.
+    pub fn solve(&mut self) -> i64 {
+        let (s, t) = (self.n, self.n + 1);
+        let mut flow = MaxFlow::new(self.n + 2);
+        for &(from, to, cap) in &self.edges {
+            flow.add_edge(from, to, cap);
+        }
+        let cut = flow.flow(s, t);
+        let mut chosen = flow.min_cut(s);
+        chosen.truncate(self.n);
+        self.chosen = Some(chosen);
+        self.base - cut
+    }
+
+    /// Whether each item is taken in the optimum found by [`ProjectSelection::solve`].
+    pub fn chosen(&self) -> Vec<bool> {
+        self.chosen.clone().expect("solve is not called yet")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::rand::XorShift64;
+
+    enum Rule {
+        Gain(usize, i64),
+        Penalty(usize, i64),
+        Pair(usize, usize, i64),
+    }
+
+    fn profit(rules: &[Rule], taken: &[bool]) -> i64 {
+        rules
+            .iter()
+            .map(|rule| match *rule {
+                Rule::Gain(i, p) if taken[i] => p,
+                Rule::Penalty(i, c) if taken[i] => -c,
+                Rule::Pair(i, j, c) if taken[i] && !taken[j] => -c,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn build(n: usize, rules: &[Rule]) -> ProjectSelection {
+        let mut ps = ProjectSelection::new(n);
+        for rule in rules {
+            match *rule {
+                Rule::Gain(i, p) => ps.gain(i, p),
+                Rule::Penalty(i, c) => ps.penalty(i, c),
+                Rule::Pair(i, j, c) => ps.must_pair_penalty(i, j, c),
+            }
+        }
+        ps
+    }
+
+    #[test]
+    fn dependency_chain() {
+        // 0 requires 1 requires 2, only 0 is profitable
+        let rules = [
+            Rule::Gain(0, 10),
+            Rule::Gain(1, -3),
+            Rule::Penalty(2, 4),
+            Rule::Pair(0, 1, i64::MAX / 4),
+            Rule::Pair(1, 2, i64::MAX / 4),
+        ];
+        let mut ps = build(3, &rules);
+        assert_eq!(ps.solve(), 3);
+        assert_eq!(ps.chosen(), vec![true, true, true]);
+
+        let rules = [
+            Rule::Gain(0, 6),
+            Rule::Gain(1, -3),
+            Rule::Penalty(2, 4),
+            Rule::Pair(0, 1, 100),
+            Rule::Pair(1, 2, 100),
+        ];
+        let mut ps = build(3, &rules);
+        assert_eq!(ps.solve(), 0);
+        assert_eq!(ps.chosen(), vec![false, false, false]);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = XorShift64::new(10);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_index(7);
+            let mut rules = vec![];
+            for _ in 0..rng.gen_index(3 * n) {
+                let i = rng.gen_index(n);
+                let v = rng.gen_range(0..21) as i64 - 10;
+                match rng.gen_index(3) {
+                    0 => rules.push(Rule::Gain(i, v)),
+                    1 => rules.push(Rule::Penalty(i, v)),
+                    _ => rules.push(Rule::Pair(i, rng.gen_index(n), v.abs())),
+                }
+            }
+            let best = (0..1usize << n)
+                .map(|bits| {
+                    let taken = (0..n).map(|i| bits >> i & 1 == 1).collect::<Vec<_>>();
+                    profit(&rules, &taken)
+                })
+                .max()
+                .unwrap();
+
+            let mut ps = build(n, &rules);
+            assert_eq!(ps.solve(), best);
+            assert_eq!(profit(&rules, &ps.chosen()), best);
+        }
+    }
+}
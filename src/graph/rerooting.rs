@@ -0,0 +1,193 @@
+//! Tree DP for every vertex as the root.
+//!
+//! The result of a rooted subtree is `add_root(merge(put_edge(child, w), ..), v)`
+//! over the children of `v`, where `merge` is a commutative monoid with identity `e`.
+
+/// All-roots tree DP in `O(n)` calls, one pass up from the leaves and one down from the root.
+pub struct Rerooting<T, W, M, P, R> {
+    // (to, weight)
+    adj: Vec<Vec<(usize, W)>>,
+    e: T,
+    merge: M,
+    put_edge: P,
+    add_root: R,
+}
+
+impl<T, W, M, P, R> Rerooting<T, W, M, P, R>
+where
+    T: Clone,
+    W: Copy,
+    M: Fn(T, T) -> T,
+    P: Fn(T, W) -> T,
+    R: Fn(T, usize) -> T,
+{
+    /// `put_edge(t, w)` carries the result `t` of a subtree over an edge of weight `w` to its parent,
+    /// `add_root(t, v)` turns the merged results of the children of `v` into the result of `v`.
+    pub fn new(n: usize, e: T, merge: M, put_edge: P, add_root: R) -> Self {
+        Self {
+            adj: (0..n).map(|_| vec![]).collect(),
+            e,
+            merge,
+            put_edge,
+            add_root,
+        }
+    }
+
+    /// Add an undirected edge of weight `w`.
+    pub fn add_edge(&mut self, u: usize, v: usize, w: W) {
+        self.adj[u].push((v, w));
+        self.adj[v].push((u, w));
+    }
+
+    /// Result of the whole tree rooted at each vertex.
+    pub fn solve(&self) -> Vec<T> {
+        let n = self.adj.len();
+        if n == 0 {
+            return vec![];
+        }
+        assert_eq!(
+            self.adj.iter().map(Vec::len).sum::<usize>(),
+            2 * (n - 1),
+            "not a tree"
+        );
+
+        // bfs from 0, the root is its own parent
+        let mut parent = vec![usize::MAX; n];
+        let mut order = vec![0];
+        parent[0] = 0;
+        let mut head = 0;
+        while let Some(&v) = order.get(head) {
+            head += 1;
+            for &(u, _) in &self.adj[v] {
+                if parent[u] == usize::MAX {
+                    parent[u] = v;
+                    order.push(u);
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "not connected");
+
+        // result of the subtree of v rooted at 0
+        let mut down = vec![self.e.clone(); n];
+        for &v in order.iter().rev() {
+            let mut acc = self.e.clone();
+            for &(c, w) in &self.adj[v] {
+                if c != parent[v] || v == 0 {
+                    acc = (self.merge)(acc, (self.put_edge)(down[c].clone(), w));
+                }
+            }
+            down[v] = (self.add_root)(acc, v);
+        }
+
+        // result of the rest of the tree seen from v, rooted at its parent
+        let mut up = vec![self.e.clone(); n];
+        let mut answer = vec![self.e.clone(); n];
+        for &v in &order {
+            let edges = &self.adj[v];
+            let carried = edges
+                .iter()
+                .map(|&(u, w)| {
+                    let t = if u == parent[v] && v != 0 {
+                        up[v].clone()
+                    } else {
+                        down[u].clone()
+                    };
+                    (self.put_edge)(t, w)
+                })
+                .collect::<Vec<_>>();
+
+            // suffix[i] merges carried[i..]
+            let mut suffix = vec![self.e.clone(); edges.len() + 1];
+            for i in (0..edges.len()).rev() {
+                suffix[i] = (self.merge)(carried[i].clone(), suffix[i + 1].clone());
+            }
+            let mut prefix = self.e.clone();
+            for (i, &(u, _)) in edges.iter().enumerate() {
+                if u != parent[v] || v == 0 {
+                    let others = (self.merge)(prefix.clone(), suffix[i + 1].clone());
+                    up[u] = (self.add_root)(others, v);
+                }
+                prefix = (self.merge)(prefix, carried[i].clone());
+            }
+            answer[v] = (self.add_root)(prefix, v);
+        }
+        answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tree::random_tree;
+    use crate::graph::Csr;
+    use crate::util::rand::XorShift64;
+
+    fn random_weighted_tree(rng: &mut XorShift64, n: usize) -> Vec<(usize, usize, i64)> {
+        random_tree(rng, n)
+            .into_iter()
+            .map(|(u, v)| (u, v, rng.gen_range(1..100) as i64))
+            .collect()
+    }
+
+    #[test]
+    fn sum_of_distances() {
+        let mut rng = XorShift64::new(35);
+        for n in 1..40 {
+            let edges = random_weighted_tree(&mut rng, n);
+            // (vertices, sum of distances to them)
+            let mut dp = Rerooting::new(
+                n,
+                (0i64, 0i64),
+                |a, b| (a.0 + b.0, a.1 + b.1),
+                |(s, d), w| (s, d + s * w),
+                |(s, d), _| (s + 1, d),
+            );
+            for &(u, v, w) in &edges {
+                dp.add_edge(u, v, w);
+            }
+            let both = edges
+                .iter()
+                .flat_map(|&(u, v, w)| [(u, v, w), (v, u, w)])
+                .collect::<Vec<_>>();
+            let g = Csr::from_edges(n, &both);
+
+            for (v, answer) in dp.solve().into_iter().enumerate() {
+                let brute = g.dijkstra_all(v).into_iter().flatten().sum::<i64>();
+                assert_eq!(answer, (n as i64, brute));
+            }
+        }
+    }
+
+    #[test]
+    fn subtree_size_product() {
+        //   0 - 1 - 2
+        //       |
+        //       3 - 4
+        // (vertices, product of the subtree sizes)
+        let mut dp = Rerooting::new(
+            5,
+            (0u64, 1u64),
+            |a, b| (a.0 + b.0, a.1 * b.1),
+            |t, ()| t,
+            |(s, p), _| (s + 1, p * (s + 1)),
+        );
+        for (u, v) in [(0, 1), (1, 2), (1, 3), (3, 4)] {
+            dp.add_edge(u, v, ());
+        }
+        let products = dp.solve().into_iter().map(|t| t.1).collect::<Vec<_>>();
+        // rooted at 0: sizes 5, 4, 1, 2, 1
+        assert_eq!(products, vec![40, 10, 40, 15, 60]);
+    }
+
+    #[test]
+    fn deep_path() {
+        let n = 200_000;
+        let mut dp = Rerooting::new(n, 0usize, usize::max, |h, ()| h + 1, |h, _| h);
+        for v in 1..n {
+            dp.add_edge(v - 1, v, ());
+        }
+        // height of the tree rooted at each vertex
+        let heights = dp.solve();
+        assert!((0..n).all(|v| heights[v] == v.max(n - 1 - v)));
+    }
+}
@@ -0,0 +1,190 @@
+//! Strongly connected components.
+
+/// Strongly connected components in topological order, as in AC Library.
+///
+/// Every edge between different components goes from a smaller id to a larger one.
+/// For 2-SAT, literal `x` is satisfiable as true iff `component_id[!x] < component_id[x]`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Scc {
+    /// Vertices of each component in ascending order.
+    pub components: Vec<Vec<usize>>,
+    /// Index into `components` of each vertex.
+    pub component_id: Vec<usize>,
+}
+
+impl Scc {
+    /// Number of components.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+/// Strongly connected components of the directed graph with `n` vertices and `edges` `(from, to)`.
+pub fn scc(n: usize, edges: &[(usize, usize)]) -> Scc {
+    let mut adj = vec![vec![]; n];
+    for &(from, to) in edges {
+        adj[from].push(to);
+    }
+    tarjan(&adj)
+}
+
+/// Iterative Tarjan, `O(n + m)` without recursion.
+pub(crate) fn tarjan(adj: &[Vec<usize>]) -> Scc {
+    const NONE: usize = usize::MAX;
+    let n = adj.len();
+    let mut ord = vec![NONE; n];
+    let mut low = vec![0; n];
+    let mut id = vec![NONE; n];
+    let mut visited = vec![];
+    let mut components: Vec<Vec<usize>> = vec![];
+    // (vertex, index of the next edge to visit)
+    let mut stack: Vec<(usize, usize)> = vec![];
+    let mut now = 0;
+
+    for root in 0..n {
+        if ord[root] != NONE {
+            continue;
+        }
+        ord[root] = now;
+        low[root] = now;
+        now += 1;
+        visited.push(root);
+        stack.push((root, 0));
+
+        while let Some(&mut (v, ref mut i)) = stack.last_mut() {
+            if let Some(&to) = adj[v].get(*i) {
+                *i += 1;
+                if ord[to] == NONE {
+                    ord[to] = now;
+                    low[to] = now;
+                    now += 1;
+                    visited.push(to);
+                    stack.push((to, 0));
+                } else if id[to] == NONE {
+                    low[v] = low[v].min(ord[to]);
+                }
+                continue;
+            }
+
+            stack.pop();
+            if let Some(&(parent, _)) = stack.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == ord[v] {
+                let mut component = vec![];
+                while let Some(u) = visited.pop() {
+                    id[u] = components.len();
+                    component.push(u);
+                    if u == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    // Tarjan finds sinks first
+    components.reverse();
+    let last = components.len().wrapping_sub(1);
+    id.iter_mut().for_each(|i| *i = last - *i);
+    Scc {
+        components,
+        component_id: id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::rand::XorShift64;
+
+    fn assert_topological(scc: &Scc, edges: &[(usize, usize)]) {
+        for &(u, v) in edges {
+            assert!(scc.component_id[u] <= scc.component_id[v], "{} -> {}", u, v);
+        }
+        for (i, component) in scc.components.iter().enumerate() {
+            assert!(component.iter().all(|&v| scc.component_id[v] == i));
+        }
+    }
+
+    #[test]
+    fn two_components() {
+        // {0, 1, 2} -> {3, 4}
+        let edges = [(3, 4), (4, 3), (1, 2), (2, 0), (0, 1), (2, 3)];
+        let scc = scc(5, &edges);
+        assert_eq!(scc.components, vec![vec![0, 1, 2], vec![3, 4]]);
+        assert_eq!(scc.component_id, vec![0, 0, 0, 1, 1]);
+        assert_topological(&scc, &edges);
+
+        // reversing the bridge reverses the order
+        let edges = [(3, 4), (4, 3), (1, 2), (2, 0), (0, 1), (3, 2)];
+        let scc = super::scc(5, &edges);
+        assert_eq!(scc.components, vec![vec![3, 4], vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn single_cycle_and_isolated() {
+        let n = 6;
+        let edges = (0..n).map(|v| (v, (v + 1) % n)).collect::<Vec<_>>();
+        let scc = scc(n, &edges);
+        assert_eq!(scc.len(), 1);
+        assert_eq!(scc.components[0], (0..n).collect::<Vec<_>>());
+
+        let scc = super::scc(3, &[]);
+        assert_eq!(scc.len(), 3);
+        assert_eq!(scc.components.concat().len(), 3);
+        assert!(super::scc(0, &[]).is_empty());
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 200_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let scc = scc(n, &edges);
+        assert_eq!(scc.component_id, (0..n).collect::<Vec<_>>());
+
+        let mut edges = edges;
+        edges.push((n - 1, 0));
+        assert_eq!(super::scc(n, &edges).len(), 1);
+    }
+
+    #[test]
+    fn random_against_reachability() {
+        let mut rng = XorShift64::new(20);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(12);
+            let edges = (0..rng.gen_index(2 * n))
+                .map(|_| (rng.gen_index(n), rng.gen_index(n)))
+                .collect::<Vec<_>>();
+            let mut reach = vec![vec![false; n]; n];
+            for (v, row) in reach.iter_mut().enumerate() {
+                row[v] = true;
+            }
+            for &(u, v) in &edges {
+                reach[u][v] = true;
+            }
+            for k in 0..n {
+                for i in 0..n {
+                    for j in 0..n {
+                        reach[i][j] |= reach[i][k] && reach[k][j];
+                    }
+                }
+            }
+
+            let scc = scc(n, &edges);
+            assert_topological(&scc, &edges);
+            for i in 0..n {
+                for j in 0..n {
+                    let same = reach[i][j] && reach[j][i];
+                    assert_eq!(scc.component_id[i] == scc.component_id[j], same);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,778 @@
+use std::ops::Range;
+use std::sync::OnceLock;
+
+const HASH_MOD: u64 = (1 << 61) - 1;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let p = a as u128 * b as u128;
+    let r = ((p >> 61) + (p & HASH_MOD as u128)) as u64;
+    if r >= HASH_MOD {
+        r - HASH_MOD
+    } else {
+        r
+    }
+}
+
+/// Random salt per subtree height, fixed for the process so hashes from different calls compare.
+fn salt(height: usize) -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+    let seed = *SEED.get_or_init(|| crate::util::rand::XorShift64::from_time().next_u64());
+    // splitmix64
+    let mut z = seed.wrapping_add((height as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    (z ^ (z >> 31)) % HASH_MOD
+}
+
+fn adjacency(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    adj
+}
+
+/// `(order, parent)` of a BFS from `root`, the root is its own parent.
+fn bfs_order(adj: &[Vec<usize>], root: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut parent = vec![usize::MAX; adj.len()];
+    let mut order = Vec::with_capacity(adj.len());
+    parent[root] = root;
+    order.push(root);
+    let mut head = 0;
+    while let Some(&v) = order.get(head) {
+        head += 1;
+        for &u in &adj[v] {
+            if parent[u] == usize::MAX {
+                parent[u] = v;
+                order.push(u);
+            }
+        }
+    }
+    (order, parent)
+}
+
+fn rooted_hash(adj: &[Vec<usize>], root: usize) -> Vec<u64> {
+    let (order, parent) = bfs_order(adj, root);
+    let mut height = vec![0; adj.len()];
+    let mut hash = vec![1; adj.len()];
+    // children are finished before their parent
+    for &v in order.iter().rev() {
+        let children = adj[v].iter().filter(|&&c| c != parent[v] || v == root);
+        let h = children.clone().map(|&c| height[c] + 1).max().unwrap_or(0);
+        height[v] = h;
+        hash[v] = children.fold(1, |acc, &c| mul_mod(acc, (salt(h) + hash[c]) % HASH_MOD));
+    }
+    hash
+}
+
+/// Hash of the subtree rooted at each vertex, equal for isomorphic rooted subtrees.
+///
+/// `hash(v) = prod(salt[height(v)] + hash(c))` over children `c` modulo `2^61 - 1`,
+/// which doesn't depend on the order of children. Salts are random per process,
+/// so collisions are unlikely even for adversarial input.
+pub fn tree_hash(n: usize, edges: &[(usize, usize)], root: usize) -> Vec<u64> {
+    assert_eq!(edges.len() + 1, n, "not a tree");
+    rooted_hash(&adjacency(n, edges), root)
+}
+
+/// Centroids of a tree, one or two adjacent vertices.
+pub fn centroids(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let adj = adjacency(n, edges);
+    let (order, parent) = bfs_order(&adj, 0);
+    let mut size = vec![1; n];
+    for &v in order.iter().skip(1).rev() {
+        size[parent[v]] += size[v];
+    }
+    (0..n)
+        .filter(|&v| {
+            let largest_child = adj[v]
+                .iter()
+                .filter(|&&c| c != parent[v] || v == 0)
+                .map(|&c| size[c])
+                .max()
+                .unwrap_or(0);
+            largest_child.max(n - size[v]) * 2 <= n
+        })
+        .collect()
+}
+
+/// Hash of the whole tree, equal for isomorphic unrooted trees.
+///
+/// Rooted at the centroid, or the smaller hash of the two centroids.
+pub fn unrooted_tree_hash(n: usize, edges: &[(usize, usize)]) -> u64 {
+    assert_eq!(edges.len() + 1, n, "not a tree");
+    let adj = adjacency(n, edges);
+    centroids(n, edges)
+        .into_iter()
+        .map(|c| rooted_hash(&adj, c)[c])
+        .min()
+        .unwrap()
+}
+
+/// Small-to-large (sack) traversal for per-subtree aggregates.
+///
+/// For each vertex `v`, calls `answer(state, v)` while `state` holds exactly the
+/// vertices of the subtree of `v`, maintained by `add(state, u)` and `remove(state, u)`.
+/// The heavy child's vertices are kept, so each vertex is added `O(log n)` times.
+pub fn dsu_on_tree<S, A, D, F>(
+    n: usize,
+    edges: &[(usize, usize)],
+    root: usize,
+    state: &mut S,
+    mut add: A,
+    mut remove: D,
+    mut answer: F,
+) where
+    A: FnMut(&mut S, usize),
+    D: FnMut(&mut S, usize),
+    F: FnMut(&mut S, usize),
+{
+    assert_eq!(edges.len() + 1, n, "not a tree");
+    let adj = adjacency(n, edges);
+    let (bfs, parent) = bfs_order(&adj, root);
+    let mut size = vec![1; n];
+    for &v in bfs.iter().skip(1).rev() {
+        size[parent[v]] += size[v];
+    }
+    // light children first, the heavy one last
+    let children = (0..n)
+        .map(|v| {
+            let mut c = adj[v]
+                .iter()
+                .copied()
+                .filter(|&c| c != parent[v] || v == root)
+                .collect::<Vec<_>>();
+            if let Some(i) = (0..c.len()).max_by_key(|&i| size[c[i]]) {
+                let last = c.len() - 1;
+                c.swap(i, last);
+            }
+            c
+        })
+        .collect::<Vec<_>>();
+
+    // preorder, the subtree of v is order[tin[v]..tin[v] + size[v]]
+    let mut order = Vec::with_capacity(n);
+    let mut tin = vec![0; n];
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        tin[v] = order.len();
+        order.push(v);
+        stack.extend(&children[v]);
+    }
+
+    // (vertex, keep, next child)
+    let mut stack = vec![(root, true, 0)];
+    while let Some(&mut (v, keep, ref mut next)) = stack.last_mut() {
+        if let Some(&c) = children[v].get(*next) {
+            *next += 1;
+            let heavy = *next == children[v].len();
+            stack.push((c, heavy, 0));
+            continue;
+        }
+        stack.pop();
+        add(state, v);
+        for &c in children[v].iter().rev().skip(1) {
+            for &u in &order[tin[c]..tin[c] + size[c]] {
+                add(state, u);
+            }
+        }
+        answer(state, v);
+        if !keep {
+            for &u in &order[tin[v]..tin[v] + size[v]] {
+                remove(state, u);
+            }
+        }
+    }
+}
+
+/// `(distance, parent)` from `from` over weighted tree edges, the parent of `from` is `usize::MAX`.
+fn farthest(adj: &[Vec<(usize, i64)>], from: usize) -> (Vec<i64>, Vec<usize>) {
+    let mut dist = vec![0; adj.len()];
+    let mut parent = vec![usize::MAX; adj.len()];
+    let mut stack = vec![from];
+    while let Some(v) = stack.pop() {
+        for &(u, w) in &adj[v] {
+            if u != parent[v] {
+                parent[u] = v;
+                dist[u] = dist[v] + w;
+                stack.push(u);
+            }
+        }
+    }
+    (dist, parent)
+}
+
+/// Length of the longest path of a tree with non-negative edge weights, and its vertices from end to end.
+///
+/// The farthest vertex from any vertex is an end of a diameter,
+/// and the farthest one from it is the other end.
+pub fn tree_diameter(n: usize, edges: &[(usize, usize, i64)]) -> (i64, Vec<usize>) {
+    assert_eq!(edges.len() + 1, n, "not a tree");
+    let mut adj = vec![vec![]; n];
+    for &(u, v, w) in edges {
+        adj[u].push((v, w));
+        adj[v].push((u, w));
+    }
+    let argmax = |dist: &[i64]| (0..n).max_by_key(|&v| dist[v]).unwrap();
+
+    let (dist, _) = farthest(&adj, 0);
+    let s = argmax(&dist);
+    let (dist, parent) = farthest(&adj, s);
+    let t = argmax(&dist);
+
+    let mut path = vec![t];
+    let mut v = t;
+    while parent[v] != usize::MAX {
+        v = parent[v];
+        path.push(v);
+    }
+    (dist[t], path)
+}
+
+/// Rooted tree with the binary lifting table for ancestor queries.
+#[derive(Clone, Debug)]
+pub struct Tree {
+    adj: Vec<Vec<usize>>,
+    root: usize,
+    depth: Vec<usize>,
+    // up[k][v] is the 2^k-th ancestor of v, or the root when it is past the root
+    up: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    /// Tree of `n` vertices and undirected `edges` rooted at `root`, in `O(n log n)`.
+    pub fn from_edges(n: usize, edges: &[(usize, usize)], root: usize) -> Self {
+        assert_eq!(edges.len() + 1, n, "not a tree");
+        let adj = adjacency(n, edges);
+        let (order, parent) = bfs_order(&adj, root);
+        assert_eq!(order.len(), n, "not connected");
+        let mut depth = vec![0; n];
+        for &v in order.iter().skip(1) {
+            depth[v] = depth[parent[v]] + 1;
+        }
+
+        let log = (usize::BITS - (n - 1).leading_zeros()).max(1) as usize;
+        let mut up = vec![parent];
+        for k in 1..log {
+            let prev = &up[k - 1];
+            let next = (0..n).map(|v| prev[prev[v]]).collect();
+            up.push(next);
+        }
+        Self {
+            adj,
+            root,
+            depth,
+            up,
+        }
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Parent of `v`, `None` for the root.
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        (v != self.root).then(|| self.up[0][v])
+    }
+
+    /// Vertices adjacent to `v`, the parent included.
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        &self.adj[v]
+    }
+
+    /// Children of `v`.
+    pub fn children(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj[v]
+            .iter()
+            .copied()
+            .filter(move |&c| self.parent(v) != Some(c))
+    }
+
+    /// Number of edges from the root to `v`.
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// Ancestor `k` edges above `v`, `None` past the root.
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        for (i, up) in self.up.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                v = up[v];
+            }
+        }
+        Some(v)
+    }
+
+    /// Lowest common ancestor of `u` and `v`.
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = if self.depth[u] >= self.depth[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        if u == v {
+            return u;
+        }
+        for up in self.up.iter().rev() {
+            if up[u] != up[v] {
+                u = up[u];
+                v = up[v];
+            }
+        }
+        self.up[0][u]
+    }
+
+    /// Number of edges on the path between `u` and `v`.
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// Preorder of the vertices, where each subtree is a contiguous range.
+    pub fn euler_tour(&self) -> EulerTour {
+        let n = self.len();
+        let mut order = Vec::with_capacity(n);
+        let mut in_time = vec![0; n];
+        let mut out_time = vec![0; n];
+        // (vertex, entered), a vertex is pushed again to be exited after its subtree
+        let mut stack = vec![(self.root, false)];
+        while let Some((v, entered)) = stack.pop() {
+            if entered {
+                out_time[v] = order.len();
+                continue;
+            }
+            in_time[v] = order.len();
+            order.push(v);
+            stack.push((v, true));
+            let children = stack.len();
+            stack.extend(self.children(v).map(|c| (c, false)));
+            // visit children in the order of the edges
+            stack[children..].reverse();
+        }
+        EulerTour {
+            order,
+            in_time,
+            out_time,
+        }
+    }
+}
+
+/// Preorder of a [`Tree`] from [`Tree::euler_tour`].
+///
+/// The subtree of `v` is `order[in_time(v)..out_time(v)]`, so subtree aggregates
+/// are range queries over values placed at `in_time`.
+#[derive(Clone, Debug)]
+pub struct EulerTour {
+    order: Vec<usize>,
+    in_time: Vec<usize>,
+    out_time: Vec<usize>,
+}
+
+impl EulerTour {
+    /// Vertices in preorder.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Position of `v` in the order.
+    pub fn in_time(&self, v: usize) -> usize {
+        self.in_time[v]
+    }
+
+    /// Position just after the last vertex of the subtree of `v`.
+    pub fn out_time(&self, v: usize) -> usize {
+        self.out_time[v]
+    }
+
+    /// Positions of the subtree of `v`.
+    pub fn subtree_range(&self, v: usize) -> Range<usize> {
+        self.in_time[v]..self.out_time[v]
+    }
+}
+
+/// Edges of a random tree on `n` vertices, each vertex attached to a smaller one.
+#[cfg(test)]
+pub(crate) fn random_tree(
+    rng: &mut crate::util::rand::XorShift64,
+    n: usize,
+) -> Vec<(usize, usize)> {
+    (1..n).map(|v| (rng.gen_index(v), v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::segment_tree::SegmentTree;
+    use crate::util::rand::XorShift64;
+    use std::collections::HashSet;
+
+    fn relabel(rng: &mut XorShift64, n: usize, edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let mut perm = (0..n).collect::<Vec<_>>();
+        rng.shuffle(&mut perm);
+        let mut edges = edges
+            .iter()
+            .map(|&(u, v)| (perm[u], perm[v]))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut edges);
+        edges
+    }
+
+    /// AHU canonical string of each rooted subtree.
+    fn canonical(n: usize, edges: &[(usize, usize)], root: usize) -> Vec<String> {
+        let adj = adjacency(n, edges);
+        let (order, parent) = bfs_order(&adj, root);
+        let mut s = vec![String::new(); n];
+        for &v in order.iter().rev() {
+            let mut children = adj[v]
+                .iter()
+                .filter(|&&c| c != parent[v] || v == root)
+                .map(|&c| s[c].clone())
+                .collect::<Vec<_>>();
+            children.sort();
+            s[v] = format!("({})", children.concat());
+        }
+        s
+    }
+
+    #[test]
+    fn isomorphic_trees() {
+        let mut rng = XorShift64::new(7);
+        for n in 1..40 {
+            let edges = random_tree(&mut rng, n);
+            let other = relabel(&mut rng, n, &edges);
+            assert_eq!(unrooted_tree_hash(n, &edges), unrooted_tree_hash(n, &other));
+        }
+
+        // path and star of 4 vertices
+        let path = [(0, 1), (1, 2), (2, 3)];
+        let star = [(0, 1), (0, 2), (0, 3)];
+        assert_ne!(unrooted_tree_hash(4, &path), unrooted_tree_hash(4, &star));
+    }
+
+    #[test]
+    fn two_centroids() {
+        // paths of even length have two centroids
+        let path = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)];
+        assert_eq!(centroids(6, &path), vec![2, 3]);
+        let reversed = [(5, 4), (4, 3), (3, 2), (2, 1), (1, 0)];
+        assert_eq!(
+            unrooted_tree_hash(6, &path),
+            unrooted_tree_hash(6, &reversed)
+        );
+
+        // rooted hashes differ by root but the unrooted one doesn't
+        let broom = [(0, 1), (1, 2), (2, 3), (3, 4), (3, 5)];
+        let relabeled = [(5, 4), (4, 3), (3, 2), (2, 0), (2, 1)];
+        assert_eq!(
+            unrooted_tree_hash(6, &broom),
+            unrooted_tree_hash(6, &relabeled)
+        );
+        assert_ne!(unrooted_tree_hash(6, &broom), unrooted_tree_hash(6, &path));
+    }
+
+    #[test]
+    fn distinct_subtrees() {
+        let mut rng = XorShift64::new(8);
+        for n in 1..60 {
+            let edges = random_tree(&mut rng, n);
+            let hashes = tree_hash(n, &edges, 0).into_iter().collect::<HashSet<_>>();
+            let strings = canonical(n, &edges, 0).into_iter().collect::<HashSet<_>>();
+            assert_eq!(hashes.len(), strings.len());
+        }
+    }
+
+    #[test]
+    fn dsu_on_tree_distinct_colors() {
+        struct Colors {
+            count: Vec<usize>,
+            distinct: usize,
+            answer: Vec<usize>,
+        }
+
+        let mut rng = XorShift64::new(9);
+        for n in 1..80 {
+            let edges = random_tree(&mut rng, n);
+            let color = (0..n).map(|_| rng.gen_index(5)).collect::<Vec<_>>();
+            let root = rng.gen_index(n);
+            let mut state = Colors {
+                count: vec![0; 5],
+                distinct: 0,
+                answer: vec![0; n],
+            };
+            dsu_on_tree(
+                n,
+                &edges,
+                root,
+                &mut state,
+                |s, v| {
+                    s.count[color[v]] += 1;
+                    if s.count[color[v]] == 1 {
+                        s.distinct += 1;
+                    }
+                },
+                |s, v| {
+                    s.count[color[v]] -= 1;
+                    if s.count[color[v]] == 0 {
+                        s.distinct -= 1;
+                    }
+                },
+                |s, v| s.answer[v] = s.distinct,
+            );
+
+            let adj = adjacency(n, &edges);
+            let (_, parent) = bfs_order(&adj, root);
+            for v in 0..n {
+                // brute force: collect the subtree of v
+                let mut seen = HashSet::new();
+                let mut stack = vec![v];
+                while let Some(u) = stack.pop() {
+                    seen.insert(color[u]);
+                    stack.extend(adj[u].iter().filter(|&&c| parent[c] == u));
+                }
+                assert_eq!(state.answer[v], seen.len());
+            }
+        }
+    }
+
+    #[test]
+    fn dsu_on_tree_long_path() {
+        let n = 200_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let mut size = vec![0; n];
+        let mut added = 0usize;
+        dsu_on_tree(
+            n,
+            &edges,
+            0,
+            &mut added,
+            |s, _| *s += 1,
+            |s, _| *s -= 1,
+            |s, v| size[v] = *s,
+        );
+        assert!((0..n).all(|v| size[v] == n - v));
+    }
+
+    #[test]
+    fn lca_hand_drawn() {
+        //         0
+        //       / | \
+        //      1  2  3
+        //     / \     \
+        //    4   5     6
+        //        |
+        //        7
+        let edges = [(0, 1), (2, 0), (0, 3), (1, 4), (5, 1), (3, 6), (5, 7)];
+        let tree = Tree::from_edges(8, &edges, 0);
+        assert_eq!(tree.lca(4, 7), 1);
+        assert_eq!(tree.lca(7, 6), 0);
+        assert_eq!(tree.lca(5, 7), 5);
+        assert_eq!(tree.lca(2, 2), 2);
+        assert_eq!(tree.depth(7), 3);
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.parent(7), Some(5));
+        assert_eq!(tree.children(1).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(tree.dist(4, 7), 3);
+        assert_eq!(tree.dist(7, 6), 5);
+
+        // rooted elsewhere
+        let tree = Tree::from_edges(8, &edges, 7);
+        assert_eq!(tree.lca(4, 6), 1);
+        assert_eq!(tree.lca(0, 2), 0);
+        assert_eq!(tree.dist(7, 6), 5);
+    }
+
+    #[test]
+    fn kth_ancestor_past_root() {
+        let edges = [(0, 1), (1, 2), (1, 3)];
+        let tree = Tree::from_edges(4, &edges, 0);
+        assert_eq!(tree.kth_ancestor(3, 0), Some(3));
+        assert_eq!(tree.kth_ancestor(3, 1), Some(1));
+        assert_eq!(tree.kth_ancestor(3, 2), Some(0));
+        assert_eq!(tree.kth_ancestor(3, 3), None);
+        assert_eq!(tree.kth_ancestor(0, 1), None);
+
+        let single = Tree::from_edges(1, &[], 0);
+        assert_eq!(single.lca(0, 0), 0);
+        assert_eq!(single.kth_ancestor(0, 1), None);
+    }
+
+    #[test]
+    fn random_against_naive() {
+        let mut rng = XorShift64::new(31);
+        for n in 1..50 {
+            let edges = random_tree(&mut rng, n);
+            let edges = relabel(&mut rng, n, &edges);
+            let root = rng.gen_index(n);
+            let tree = Tree::from_edges(n, &edges, root);
+            let ancestors = |mut v: usize| {
+                let mut path = vec![v];
+                while let Some(p) = tree.parent(v) {
+                    path.push(p);
+                    v = p;
+                }
+                path
+            };
+            for _ in 0..20 {
+                let (u, v) = (rng.gen_index(n), rng.gen_index(n));
+                let (au, av) = (ancestors(u), ancestors(v));
+                let lca = *au.iter().find(|a| av.contains(a)).unwrap();
+                assert_eq!(tree.lca(u, v), lca);
+                assert_eq!(tree.dist(u, v), tree.dist(v, u));
+                let k = rng.gen_index(n + 1);
+                assert_eq!(tree.kth_ancestor(u, k), au.get(k).copied());
+            }
+        }
+    }
+
+    #[test]
+    fn deep_path() {
+        let n = 200_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let tree = Tree::from_edges(n, &edges, 0);
+        assert_eq!(tree.depth(n - 1), n - 1);
+        assert_eq!(tree.lca(n - 1, n / 2), n / 2);
+        assert_eq!(tree.kth_ancestor(n - 1, n - 1), Some(0));
+        assert_eq!(tree.dist(0, n - 1), n - 1);
+    }
+
+    #[test]
+    fn diameter_star() {
+        let edges = [(0, 1, 3), (0, 2, 5), (3, 0, 4), (0, 4, 1)];
+        let (d, path) = tree_diameter(5, &edges);
+        assert_eq!(d, 9);
+        let mut ends = [path[0], path[2]];
+        ends.sort_unstable();
+        assert_eq!((ends, path[1]), ([2, 3], 0));
+    }
+
+    #[test]
+    fn diameter_weighted_path() {
+        // the heavy edge makes the middle of the path count more than its length
+        let edges = [(0, 1, 1), (1, 2, 10), (2, 3, 1), (1, 4, 2)];
+        let (d, path) = tree_diameter(5, &edges);
+        assert_eq!(d, 13);
+        assert!(path == vec![4, 1, 2, 3] || path == vec![3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn diameter_small() {
+        assert_eq!(tree_diameter(1, &[]), (0, vec![0]));
+        let (d, mut path) = tree_diameter(2, &[(1, 0, 7)]);
+        path.sort_unstable();
+        assert_eq!((d, path), (7, vec![0, 1]));
+    }
+
+    #[test]
+    fn diameter_path_weights_sum() {
+        let mut rng = XorShift64::new(32);
+        for n in 1..60 {
+            let edges = random_tree(&mut rng, n)
+                .into_iter()
+                .map(|(u, v)| (u, v, rng.gen_range(0..100) as i64))
+                .collect::<Vec<_>>();
+            let (d, path) = tree_diameter(n, &edges);
+            let weight = |u: usize, v: usize| {
+                edges
+                    .iter()
+                    .find(|&&(a, b, _)| (a, b) == (u, v) || (a, b) == (v, u))
+                    .unwrap()
+                    .2
+            };
+            assert_eq!(path.windows(2).map(|e| weight(e[0], e[1])).sum::<i64>(), d);
+
+            // brute force: farthest pair over all roots
+            let mut adj = vec![vec![]; n];
+            for &(u, v, w) in &edges {
+                adj[u].push((v, w));
+                adj[v].push((u, w));
+            }
+            let brute = (0..n)
+                .map(|s| *farthest(&adj, s).0.iter().max().unwrap())
+                .max()
+                .unwrap();
+            assert_eq!(d, brute);
+        }
+    }
+
+    #[test]
+    fn euler_tour_ranges() {
+        //       0
+        //     / | \
+        //    1  2  3
+        //   / \    |
+        //  4   5   6
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6)];
+        let tree = Tree::from_edges(7, &edges, 0);
+        let tour = tree.euler_tour();
+        assert_eq!(tour.order(), &[0, 1, 4, 5, 2, 3, 6]);
+        assert_eq!(tour.subtree_range(0), 0..7);
+        assert_eq!(tour.subtree_range(1), 1..4);
+        assert_eq!(tour.subtree_range(6), 6..7);
+        assert_eq!((tour.in_time(3), tour.out_time(3)), (5, 7));
+
+        // ranges of siblings are disjoint
+        let mut rng = XorShift64::new(33);
+        for n in 1..50 {
+            let edges = random_tree(&mut rng, n);
+            let tree = Tree::from_edges(n, &edges, rng.gen_index(n));
+            let tour = tree.euler_tour();
+            assert_eq!(tour.subtree_range(tree.root()), 0..n);
+            for v in 0..n {
+                assert_eq!(tour.order()[tour.in_time(v)], v);
+                let mut children = tree
+                    .children(v)
+                    .map(|c| tour.subtree_range(c))
+                    .collect::<Vec<_>>();
+                children.sort_by_key(|r| r.start);
+                let inner = tour.in_time(v) + 1..tour.out_time(v);
+                let mut next = inner.start;
+                for r in children {
+                    assert_eq!(r.start, next);
+                    next = r.end;
+                }
+                assert_eq!(next, inner.end);
+            }
+        }
+    }
+
+    #[test]
+    fn euler_tour_subtree_sum() {
+        let mut rng = XorShift64::new(34);
+        let n = 300;
+        let edges = random_tree(&mut rng, n);
+        let tree = Tree::from_edges(n, &edges, 0);
+        let tour = tree.euler_tour();
+        let mut value = vec![0i64; n];
+        let mut seg = SegmentTree::new(n, 0i64, |a, b| a + b);
+
+        for _ in 0..500 {
+            let v = rng.gen_index(n);
+            value[v] = rng.gen_range(0..1000) as i64;
+            seg.update(tour.in_time(v), value[v]);
+
+            // any ancestor of v sees the new value
+            let a = tree
+                .kth_ancestor(v, rng.gen_index(tree.depth(v) + 1))
+                .unwrap();
+            let brute = (0..n)
+                .filter(|&u| tree.depth(u) >= tree.depth(a))
+                .filter(|&u| tree.kth_ancestor(u, tree.depth(u) - tree.depth(a)) == Some(a))
+                .map(|u| value[u])
+                .sum::<i64>();
+            assert_eq!(seg.query(tour.subtree_range(a)), brute);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+
+use super::adjacency::{self, Adjacency};
+
+/// Undirected graph with `i64` edge costs.
+///
+/// Each edge is stored once and numbered in insertion order,
+/// so parallel edges stay distinguishable by their ids.
+#[derive(Clone)]
+pub struct Graph {
+    // (neighbor, edge id)
+    adj: Vec<Vec<(usize, usize)>>,
+    edges: Vec<(usize, usize, i64)>,
+}
+
+impl Graph {
+    pub fn new(size: usize) -> Self {
+        Self {
+            adj: vec![vec![]; size],
+            edges: vec![],
+        }
+    }
+
+    /// Add an edge between `u` and `v` and return its id. A self loop appears twice in `adj[u]`.
+    pub fn add_edge(&mut self, u: usize, v: usize, cost: i64) -> usize {
+        let id = self.edges.len();
+        self.edges.push((u, v, cost));
+        self.adj[u].push((v, id));
+        self.adj[v].push((u, id));
+        id
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// `(u, v, cost)` of the edge `id` as it was added.
+    pub fn edge(&self, id: usize) -> (usize, usize, i64) {
+        self.edges[id]
+    }
+
+    /// `(u, v, cost)` of every edge in insertion order.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, i64)> + '_ {
+        self.edges.iter().copied()
+    }
+
+    /// `(neighbor, cost)` of the edges incident to `v`.
+    pub fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, i64)> + '_ {
+        self.adj[v]
+            .iter()
+            .map(move |&(to, id)| (to, self.edges[id].2))
+    }
+
+    /// `(neighbor, edge id)` of the edges incident to `v`.
+    pub fn incident(&self, v: usize) -> &[(usize, usize)] {
+        &self.adj[v]
+    }
+}
+
+impl Graph {
+    /// Shortest distances from `from` when every edge costs 0 or 1, `None` if unreachable.
+    pub fn bfs01(&self, from: usize) -> Vec<Option<i64>> {
+        adjacency::bfs01(self, from)
+    }
+
+    /// Number of edges on a shortest path from `from` to each vertex ignoring costs, `None` if unreachable.
+    pub fn bfs(&self, from: usize) -> Vec<Option<u32>> {
+        self.bfs_parents(from).0
+    }
+
+    /// Vertices of a path from `from` to `to` with the fewest edges, both ends included.
+    pub fn bfs_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let (dist, parent) = self.bfs_parents(from);
+        adjacency::path_to(&dist, &parent, to)
+    }
+
+    fn bfs_parents(&self, from: usize) -> (Vec<Option<u32>>, Vec<usize>) {
+        adjacency::bfs_parents(self, &[from])
+    }
+
+    /// Color 0 or 1 of each vertex with adjacent vertices colored differently,
+    /// `None` if the graph has an odd cycle, including a self loop.
+    ///
+    /// The smallest vertex of each connected component is colored 0.
+    pub fn two_coloring(&self) -> Option<Vec<u8>> {
+        self.color_components().map(|(color, _)| color)
+    }
+
+    /// Number of vertices of color 0 and 1 in each connected component of [`Graph::two_coloring`],
+    /// components in the order of their smallest vertices.
+    pub fn two_coloring_counts(&self) -> Option<Vec<[usize; 2]>> {
+        let (color, component) = self.color_components()?;
+        let mut counts = vec![];
+        for (v, &c) in component.iter().enumerate() {
+            if c == counts.len() {
+                counts.push([0, 0]);
+            }
+            counts[c][color[v] as usize] += 1;
+        }
+        Some(counts)
+    }
+
+    /// `(color, component)` of each vertex.
+    fn color_components(&self) -> Option<(Vec<u8>, Vec<usize>)> {
+        const NONE: usize = usize::MAX;
+        let mut color = vec![0; self.len()];
+        let mut component = vec![NONE; self.len()];
+        let mut count = 0;
+        for root in 0..self.len() {
+            if component[root] != NONE {
+                continue;
+            }
+            component[root] = count;
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for &(u, _) in &self.adj[v] {
+                    if component[u] == NONE {
+                        component[u] = count;
+                        color[u] = color[v] ^ 1;
+                        stack.push(u);
+                    } else if color[u] == color[v] {
+                        return None;
+                    }
+                }
+            }
+            count += 1;
+        }
+        Some((color, component))
+    }
+}
+
+/// [`Graph::two_coloring`] of `n` vertices and undirected `edges`.
+pub fn bipartite_coloring(n: usize, edges: &[(usize, usize)]) -> Option<Vec<u8>> {
+    let mut g = Graph::new(n);
+    for &(u, v) in edges {
+        g.add_edge(u, v, 1);
+    }
+    g.two_coloring()
+}
+
+/// Each undirected edge is an edge in both directions.
+impl Adjacency for Graph {
+    type Weight = i64;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn for_each_neighbor<F: FnMut(usize, i64)>(&self, v: usize, mut f: F) {
+        self.adj[v]
+            .iter()
+            .for_each(|&(to, id)| f(to, self.edges[id].2));
+    }
+}
+
+impl fmt::Debug for Graph {
+    /// `Graph [(u, v, cost), ..]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Graph ")?;
+        f.debug_list().entries(&self.edges).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_and_neighbors() {
+        let mut g = Graph::new(4);
+        assert_eq!(g.add_edge(0, 1, 5), 0);
+        assert_eq!(g.add_edge(1, 2, 3), 1);
+        assert_eq!(g.add_edge(0, 1, 2), 2);
+        assert_eq!(g.add_edge(2, 2, 1), 3);
+
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.edge(2), (0, 1, 2));
+        assert_eq!(
+            g.neighbors(1).collect::<Vec<_>>(),
+            vec![(0, 5), (2, 3), (0, 2)]
+        );
+        assert_eq!(g.incident(2), &[(1, 1), (2, 3), (2, 3)]);
+        assert_eq!(g.neighbors(3).count(), 0);
+        assert_eq!(
+            format!("{:?}", g),
+            "Graph [(0, 1, 5), (1, 2, 3), (0, 1, 2), (2, 2, 1)]"
+        );
+    }
+
+    fn assert_proper(g: &Graph, color: &[u8]) {
+        for (u, v, _) in g.edges() {
+            assert_ne!(color[u], color[v], "{} - {}", u, v);
+        }
+    }
+
+    fn cycle(n: usize) -> Graph {
+        let mut g = Graph::new(n);
+        for v in 0..n {
+            g.add_edge(v, (v + 1) % n, 1);
+        }
+        g
+    }
+
+    #[test]
+    fn two_coloring_cycles() {
+        let g = cycle(6);
+        assert_eq!(g.two_coloring(), Some(vec![0, 1, 0, 1, 0, 1]));
+        assert_eq!(g.two_coloring_counts(), Some(vec![[3, 3]]));
+
+        assert_eq!(cycle(5).two_coloring(), None);
+        assert_eq!(cycle(5).two_coloring_counts(), None);
+        assert_eq!(bipartite_coloring(3, &[(0, 1), (1, 2), (2, 0)]), None);
+    }
+
+    #[test]
+    fn two_coloring_forest() {
+        // star 0 - {1, 2, 3}, path 4 - 5, isolated 6
+        let edges = [(0, 1), (2, 0), (0, 3), (5, 4)];
+        let color = bipartite_coloring(7, &edges).unwrap();
+        assert_eq!(color, vec![0, 1, 1, 1, 0, 1, 0]);
+
+        let mut g = Graph::new(7);
+        for &(u, v) in &edges {
+            g.add_edge(u, v, 1);
+        }
+        assert_proper(&g, &color);
+        assert_eq!(g.two_coloring_counts(), Some(vec![[1, 3], [1, 1], [1, 0]]));
+    }
+
+    #[test]
+    fn two_coloring_self_loop() {
+        assert_eq!(bipartite_coloring(1, &[(0, 0)]), None);
+        assert_eq!(bipartite_coloring(3, &[(0, 1), (2, 2)]), None);
+        // parallel edges are fine
+        assert_eq!(bipartite_coloring(2, &[(0, 1), (1, 0)]), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn two_coloring_long_path() {
+        let n = 200_000;
+        let mut g = Graph::new(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v, 1);
+        }
+        let color = g.two_coloring().unwrap();
+        assert_proper(&g, &color);
+        assert_eq!(g.two_coloring_counts(), Some(vec![[n / 2, n / 2]]));
+    }
+
+    #[test]
+    fn bfs01() {
+        //   0 -1- 1 -0- 2
+        //   |           |
+        //   +-----1-----3 -0- 4
+        let mut g = Graph::new(6);
+        for (u, v, c) in [(0, 1, 1), (1, 2, 0), (2, 3, 1), (0, 3, 1), (3, 4, 0)] {
+            g.add_edge(u, v, c);
+        }
+        assert_eq!(
+            g.bfs01(2),
+            vec![Some(1), Some(0), Some(0), Some(1), Some(1), None]
+        );
+    }
+
+    #[test]
+    fn bfs() {
+        // cycle 0 - 1 - 2 - 3 - 4 - 0 and isolated 5
+        let mut g = Graph::new(6);
+        for v in 0..5 {
+            g.add_edge(v, (v + 1) % 5, 1);
+        }
+        g.add_edge(5, 5, 1);
+        assert_eq!(
+            g.bfs(0),
+            vec![Some(0), Some(1), Some(2), Some(2), Some(1), None]
+        );
+        assert_eq!(g.bfs_path(0, 3), Some(vec![0, 4, 3]));
+        assert_eq!(g.bfs_path(1, 1), Some(vec![1]));
+        assert_eq!(g.bfs_path(0, 5), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Hill climbing and simulated annealing for heuristic contests.
+
+use std::time::Duration;
+
+use crate::util::{rand::XorShift64, timer::Timer};
+
+// proposals between clock reads, as `Instant::now` is slow next to a cheap proposal
+const CHECK_INTERVAL: u64 = 64;
+const DEFAULT_SNAPSHOT_INTERVAL: u64 = 1 << 12;
+
+#[derive(Clone, Copy, Debug)]
+enum Cooling {
+    HillClimbing,
+    Linear { start: f64, end: f64 },
+    Exponential { start: f64, end: f64 },
+}
+
+/// Temperature schedule and run limits of [`anneal`].
+#[derive(Clone, Debug)]
+pub struct Schedule {
+    cooling: Cooling,
+    seed: u64,
+    max_iterations: Option<u64>,
+    snapshot_interval: u64,
+}
+
+impl Schedule {
+    /// Only accept proposals that don't lower the score.
+    pub fn hill_climbing() -> Self {
+        Self::with_cooling(Cooling::HillClimbing)
+    }
+
+    /// Temperature moving linearly from `start` to `end`.
+    pub fn linear(start: f64, end: f64) -> Self {
+        Self::with_cooling(Cooling::Linear { start, end })
+    }
+
+    /// Temperature moving geometrically from `start` to `end`, both must be positive.
+    pub fn exponential(start: f64, end: f64) -> Self {
+        assert!(start > 0.0 && end > 0.0);
+        Self::with_cooling(Cooling::Exponential { start, end })
+    }
+
+    fn with_cooling(cooling: Cooling) -> Self {
+        Self {
+            cooling,
+            seed: XorShift64::default().next_u64(),
+            max_iterations: None,
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Stop after `n` proposals even if time remains.
+    ///
+    /// The schedule then follows the iteration count instead of the clock,
+    /// which makes runs reproducible as long as the timer doesn't expire first.
+    pub fn max_iterations(mut self, n: u64) -> Self {
+        self.max_iterations = Some(n);
+        self
+    }
+
+    /// Pass the best state to the snapshot callback of [`anneal_with_snapshots`] every `n` proposals.
+    pub fn snapshot_interval(mut self, n: u64) -> Self {
+        assert!(n > 0);
+        self.snapshot_interval = n;
+        self
+    }
+
+    fn temperature(&self, progress: f64) -> f64 {
+        match self.cooling {
+            Cooling::HillClimbing => 0.0,
+            Cooling::Linear { start, end } => start + (end - start) * progress,
+            Cooling::Exponential { start, end } => start * (end / start).powf(progress),
+        }
+    }
+}
+
+/// Maximize `score` from `initial` until `timer` expires, returning the best state and its score.
+///
+/// A proposal is accepted if it doesn't lower the score, or with probability
+/// `exp((new - old) / T)` otherwise.
+/// The clock is read every 64 proposals, so the run may end that many proposals late.
+pub fn anneal<S, F, P>(
+    initial: S,
+    score: F,
+    propose: P,
+    timer: &Timer,
+    schedule: Schedule,
+) -> (S, f64)
+where
+    S: Clone,
+    F: Fn(&S) -> f64,
+    P: FnMut(&S, &mut XorShift64) -> S,
+{
+    anneal_with_snapshots(initial, score, propose, timer, schedule, |_, _, _| {})
+}
+
+/// [`anneal`] that also calls `snapshot(elapsed, best, best_score)` every
+/// [`Schedule::snapshot_interval`] proposals and once at the end,
+/// e.g. to print the best answer so far or to plot the progress of the score.
+pub fn anneal_with_snapshots<S, F, P, C>(
+    initial: S,
+    score: F,
+    mut propose: P,
+    timer: &Timer,
+    schedule: Schedule,
+    mut snapshot: C,
+) -> (S, f64)
+where
+    S: Clone,
+    F: Fn(&S) -> f64,
+    P: FnMut(&S, &mut XorShift64) -> S,
+    C: FnMut(Duration, &S, f64),
+{
+    let mut rng = XorShift64::new(schedule.seed);
+    let mut current_score = score(&initial);
+    let mut best = (initial.clone(), current_score);
+    let mut current = initial;
+    let mut iterations = 0;
+    let mut time_progress = 0.0;
+
+    loop {
+        if iterations % CHECK_INTERVAL == 0 {
+            time_progress = timer.progress();
+            if time_progress >= 1.0 {
+                break;
+            }
+        }
+        let progress = match schedule.max_iterations {
+            Some(max) if iterations >= max => break,
+            Some(max) => iterations as f64 / max as f64,
+            None => time_progress,
+        };
+        if iterations > 0 && iterations % schedule.snapshot_interval == 0 {
+            snapshot(timer.elapsed(), &best.0, best.1);
+        }
+        iterations += 1;
+
+        let next = propose(&current, &mut rng);
+        let next_score = score(&next);
+        let t = schedule.temperature(progress);
+        let accept = next_score >= current_score
+            || (t > 0.0 && rng.next_f64() < ((next_score - current_score) / t).exp());
+        if accept {
+            current = next;
+            current_score = next_score;
+            if current_score > best.1 {
+                best = (current.clone(), current_score);
+            }
+        }
+    }
+    snapshot(timer.elapsed(), &best.0, best.1);
+    best
+}
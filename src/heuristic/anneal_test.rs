@@ -0,0 +1,109 @@
+use super::anneal::{anneal, anneal_with_snapshots, Schedule};
+use crate::util::{rand::XorShift64, timer::Timer};
+use std::time::Duration;
+
+/// Negated total of adjacent differences, maximized by a sorted order.
+fn score(order: &[i64]) -> f64 {
+    -order.windows(2).map(|w| (w[0] - w[1]).abs()).sum::<i64>() as f64
+}
+
+/// Reverse a random segment.
+fn propose(order: &[i64], rng: &mut XorShift64) -> Vec<i64> {
+    let mut next = order.to_vec();
+    let l = rng.gen_index(next.len());
+    let r = rng.gen_index(next.len());
+    next[l.min(r)..=l.max(r)].reverse();
+    next
+}
+
+fn initial() -> Vec<i64> {
+    let mut rng = XorShift64::new(17);
+    let mut v = (0..12)
+        .map(|_| rng.gen_range(0..1000) as i64)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut v);
+    v
+}
+
+#[test]
+fn reaches_optimum() {
+    let v = initial();
+    let optimum = -(v.iter().max().unwrap() - v.iter().min().unwrap()) as f64;
+
+    for schedule in [
+        Schedule::hill_climbing(),
+        Schedule::linear(300.0, 1.0),
+        Schedule::exponential(300.0, 1.0),
+    ] {
+        let timer = Timer::new(Duration::from_secs(60));
+        let (best, s) = anneal(
+            v.clone(),
+            |v| score(v),
+            |v, rng| propose(v, rng),
+            &timer,
+            schedule.seed(1).max_iterations(20_000),
+        );
+        assert_eq!(s, optimum);
+        assert_eq!(score(&best), s);
+    }
+}
+
+#[test]
+fn deterministic_with_seed_and_iteration_cap() {
+    let run = || {
+        let timer = Timer::new(Duration::from_secs(60));
+        let schedule = Schedule::linear(100.0, 1.0).seed(42).max_iterations(300);
+        anneal(
+            initial(),
+            |v| score(v),
+            |v, rng| propose(v, rng),
+            &timer,
+            schedule,
+        )
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn snapshots_of_the_best() {
+    let timer = Timer::new(Duration::from_secs(60));
+    let schedule = Schedule::linear(300.0, 1.0)
+        .seed(3)
+        .max_iterations(1000)
+        .snapshot_interval(100);
+    let mut snapshots = vec![];
+    let (best, s) = anneal_with_snapshots(
+        initial(),
+        |v| score(v),
+        |v, rng| propose(v, rng),
+        &timer,
+        schedule,
+        |elapsed, state, s| {
+            assert_eq!(score(state), s);
+            snapshots.push((elapsed, s));
+        },
+    );
+
+    // every 100 proposals and once at the end
+    assert_eq!(snapshots.len(), 10);
+    assert!(snapshots
+        .windows(2)
+        .all(|w| w[0].0 <= w[1].0 && w[0].1 <= w[1].1));
+    assert_eq!(snapshots.last().unwrap().1, s);
+    assert_eq!(score(&best), s);
+}
+
+#[test]
+fn stops_when_timer_expires() {
+    let timer = Timer::new(Duration::ZERO);
+    let v = initial();
+    let (best, s) = anneal(
+        v.clone(),
+        |v| score(v),
+        |v, rng| propose(v, rng),
+        &timer,
+        Schedule::hill_climbing(),
+    );
+    assert_eq!(best, v);
+    assert_eq!(s, score(&v));
+}
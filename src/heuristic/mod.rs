@@ -0,0 +1,6 @@
+pub mod anneal;
+
+pub use anneal::{anneal, anneal_with_snapshots, Schedule};
+
+#[cfg(test)]
+mod anneal_test;
@@ -1,4 +1,9 @@
 pub mod calc;
 pub mod collections;
 pub mod contest;
+pub mod dp;
 pub mod graph;
+pub mod heuristic;
+pub mod prelude;
+pub mod search;
+pub mod util;
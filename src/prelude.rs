@@ -0,0 +1,8 @@
+pub use crate::calc::gcd::gcd;
+pub use crate::calc::prime::PrimeFactorizer;
+pub use crate::collections::segment_tree::SegmentTree;
+pub use crate::collections::unionfind::UnionFind;
+pub use crate::contest::cio::{self, FromScanner, Scanner, Writer};
+pub use crate::graph::dag::Dag;
+pub use crate::graph::undirected::Graph;
+pub use crate::{debug, debug_grid, setup};
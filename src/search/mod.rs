@@ -0,0 +1,7 @@
+pub mod subset_sum;
+pub mod two_pointer;
+
+#[cfg(test)]
+mod subset_sum_test;
+#[cfg(test)]
+mod two_pointer_test;
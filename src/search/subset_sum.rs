@@ -0,0 +1,59 @@
+//! Subset sums by meet in the middle, `O(2^(n/2))` for `n` up to about 40.
+//!
+//! The empty subset is included and sums to 0.
+
+/// All `2^n` subset sums in increasing order, with duplicates.
+pub fn subset_sums(a: &[i64]) -> Vec<i64> {
+    let mut sums = vec![0];
+    for &x in a {
+        // merge the sums without x and with x, both sorted
+        let mut merged = Vec::with_capacity(sums.len() * 2);
+        let (mut i, mut j) = (0, 0);
+        while i < sums.len() || j < sums.len() {
+            if j == sums.len() || (i < sums.len() && sums[i] <= sums[j] + x) {
+                merged.push(sums[i]);
+                i += 1;
+            } else {
+                merged.push(sums[j] + x);
+                j += 1;
+            }
+        }
+        sums = merged;
+    }
+    sums
+}
+
+fn halves(a: &[i64]) -> (Vec<i64>, Vec<i64>) {
+    let (l, r) = a.split_at(a.len() / 2);
+    (subset_sums(l), subset_sums(r))
+}
+
+/// Number of subsets whose sum is at most `k`.
+pub fn count_subsets_sum_at_most(a: &[i64], k: i64) -> u64 {
+    let (l, r) = halves(a);
+    // for increasing l[i], the fitting prefix of r only shrinks
+    let mut fit = r.len();
+    let mut count = 0;
+    for x in l {
+        while fit > 0 && x + r[fit - 1] > k {
+            fit -= 1;
+        }
+        count += fit as u64;
+    }
+    count
+}
+
+/// Whether some subset sums to exactly `k`.
+pub fn exists_subset_sum(a: &[i64], k: i64) -> bool {
+    let (l, r) = halves(a);
+    let mut j = r.len();
+    for x in l {
+        while j > 0 && x + r[j - 1] > k {
+            j -= 1;
+        }
+        if j > 0 && x + r[j - 1] == k {
+            return true;
+        }
+    }
+    false
+}
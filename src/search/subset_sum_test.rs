@@ -0,0 +1,59 @@
+use super::subset_sum::{count_subsets_sum_at_most, exists_subset_sum, subset_sums};
+use crate::util::rand::XorShift64;
+
+fn brute(a: &[i64]) -> Vec<i64> {
+    let mut sums = (0..1usize << a.len())
+        .map(|mask| {
+            (0..a.len())
+                .filter(|&i| mask >> i & 1 == 1)
+                .map(|i| a[i])
+                .sum()
+        })
+        .collect::<Vec<i64>>();
+    sums.sort_unstable();
+    sums
+}
+
+#[test]
+fn against_brute_force() {
+    let mut rng = XorShift64::new(15);
+    for _ in 0..30 {
+        let n = rng.gen_index(21);
+        let a = (0..n)
+            .map(|_| rng.gen_range(0..21) as i64 - 10)
+            .collect::<Vec<_>>();
+        let sums = brute(&a);
+        assert_eq!(subset_sums(&a), sums);
+
+        for k in -40..=40 {
+            let count = sums.partition_point(|&s| s <= k) as u64;
+            assert_eq!(count_subsets_sum_at_most(&a, k), count, "{:?} {}", a, k);
+            let exists = sums.binary_search(&k).is_ok();
+            assert_eq!(exists_subset_sum(&a, k), exists, "{:?} {}", a, k);
+        }
+    }
+}
+
+#[test]
+fn all_negative() {
+    let a = [-3, -5, -7, -5];
+    assert_eq!(count_subsets_sum_at_most(&a, 0), 16);
+    assert_eq!(count_subsets_sum_at_most(&a, -1), 15);
+    assert_eq!(count_subsets_sum_at_most(&a, -20), 1);
+    assert_eq!(count_subsets_sum_at_most(&a, -21), 0);
+    assert!(exists_subset_sum(&a, -10));
+    assert!(exists_subset_sum(&a, 0));
+    assert!(!exists_subset_sum(&a, -11));
+}
+
+#[test]
+fn forty_elements() {
+    let mut rng = XorShift64::new(16);
+    let a = (0..40)
+        .map(|_| rng.gen_range(0..1_000_000_000) as i64)
+        .collect::<Vec<_>>();
+    let total = a.iter().sum::<i64>();
+    assert_eq!(count_subsets_sum_at_most(&a, total), 1 << 40);
+    assert_eq!(count_subsets_sum_at_most(&a, -1), 0);
+    assert!(exists_subset_sum(&a, total - a[3] - a[30]));
+}
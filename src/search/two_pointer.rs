@@ -0,0 +1,82 @@
+//! Two pointer (shakutori) drivers over windows `[l, r)` of `0..n`.
+//!
+//! The window aggregate lives in `state`, updated by `add(state, i)` and `remove(state, i)`.
+//! This differs from plain closures over captured variables on purpose: `add` and `ok`
+//! would both need to borrow the aggregate, which the borrow checker rejects.
+//!
+//! Both pointers only move forward, so there are `O(n)` callback calls in total.
+//! [`two_pointer`] tries to add the element at `r` and removes it again when the window fails,
+//! so `add` and `remove` are called at most `2n` times each.
+
+/// For each `l`, extend `r` as far as `ok(state)` holds and call `record(state, l, r)`.
+///
+/// Requires that `ok` holding for a window implies it holds for its sub-windows.
+/// An element which alone fails `ok` is recorded as the empty window `[l, l)`.
+pub fn two_pointer<S, A, D, P, F>(
+    n: usize,
+    state: &mut S,
+    mut add: A,
+    mut remove: D,
+    mut ok: P,
+    mut record: F,
+) where
+    A: FnMut(&mut S, usize),
+    D: FnMut(&mut S, usize),
+    P: FnMut(&S) -> bool,
+    F: FnMut(&S, usize, usize),
+{
+    let mut r = 0;
+    for l in 0..n {
+        while r < n {
+            add(state, r);
+            if ok(state) {
+                r += 1;
+            } else {
+                remove(state, r);
+                break;
+            }
+        }
+        record(state, l, r);
+        if r == l {
+            // the window is empty, skip l without touching the state
+            r += 1;
+        } else {
+            remove(state, l);
+        }
+    }
+}
+
+/// For each `l`, the smallest `r` such that `ok(state)` holds for `[l, r)`, passed to `record`.
+///
+/// Requires that `ok` holding for a window implies it holds for its super-windows.
+/// Stops at the first `l` for which no such `r` exists.
+pub fn two_pointer_min<S, A, D, P, F>(
+    n: usize,
+    state: &mut S,
+    mut add: A,
+    mut remove: D,
+    mut ok: P,
+    mut record: F,
+) where
+    A: FnMut(&mut S, usize),
+    D: FnMut(&mut S, usize),
+    P: FnMut(&S) -> bool,
+    F: FnMut(&S, usize, usize),
+{
+    let mut r = 0;
+    for l in 0..n {
+        while r < n && !ok(state) {
+            add(state, r);
+            r += 1;
+        }
+        if !ok(state) {
+            return;
+        }
+        record(state, l, r);
+        if r == l {
+            r += 1;
+        } else {
+            remove(state, l);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use super::two_pointer::{two_pointer, two_pointer_min};
+use crate::util::rand::XorShift64;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn longest_sum_at_most_k() {
+    let mut rng = XorShift64::new(1);
+    for _ in 0..200 {
+        let n = rng.gen_index(30);
+        let a = (0..n).map(|_| rng.gen_range(0..10)).collect::<Vec<_>>();
+        let k = rng.gen_range(0..30);
+
+        let mut best = 0;
+        two_pointer(
+            n,
+            &mut 0,
+            |sum, i| *sum += a[i],
+            |sum, i| *sum -= a[i],
+            |&sum| sum <= k,
+            |_, l, r| best = best.max(r - l),
+        );
+
+        let brute = (0..n)
+            .flat_map(|l| (l..=n).map(move |r| (l, r)))
+            .filter(|&(l, r)| a[l..r].iter().sum::<u64>() <= k)
+            .map(|(l, r)| r - l)
+            .max()
+            .unwrap_or(0);
+        assert_eq!(best, brute, "{:?} {}", a, k);
+    }
+}
+
+#[test]
+fn subarrays_at_most_k_distinct() {
+    let mut rng = XorShift64::new(2);
+    for _ in 0..200 {
+        let n = rng.gen_index(30);
+        let a = (0..n).map(|_| rng.gen_range(0..5)).collect::<Vec<_>>();
+        let k = rng.gen_index(4);
+
+        let mut count = 0;
+        two_pointer(
+            n,
+            &mut HashMap::new(),
+            |freq, i| *freq.entry(a[i]).or_insert(0) += 1,
+            |freq, i| {
+                let c = freq.get_mut(&a[i]).unwrap();
+                *c -= 1;
+                if *c == 0 {
+                    freq.remove(&a[i]);
+                }
+            },
+            |freq| freq.len() <= k,
+            |_, l, r| count += r - l,
+        );
+
+        let brute = (0..n)
+            .flat_map(|l| (l + 1..=n).map(move |r| (l, r)))
+            .filter(|&(l, r)| a[l..r].iter().collect::<HashSet<_>>().len() <= k)
+            .count();
+        assert_eq!(count, brute, "{:?} {}", a, k);
+    }
+}
+
+#[test]
+fn shortest_sum_at_least_k() {
+    let mut rng = XorShift64::new(3);
+    for _ in 0..200 {
+        let n = rng.gen_index(30);
+        let a = (0..n).map(|_| rng.gen_range(0..10)).collect::<Vec<_>>();
+        let k = rng.gen_range(1..40);
+
+        let mut best = None;
+        two_pointer_min(
+            n,
+            &mut 0,
+            |sum, i| *sum += a[i],
+            |sum, i| *sum -= a[i],
+            |&sum| sum >= k,
+            |_, l, r| best = Some(best.unwrap_or(usize::MAX).min(r - l)),
+        );
+
+        let brute = (0..n)
+            .flat_map(|l| (l..=n).map(move |r| (l, r)))
+            .filter(|&(l, r)| a[l..r].iter().sum::<u64>() >= k)
+            .map(|(l, r)| r - l)
+            .min();
+        assert_eq!(best, brute, "{:?} {}", a, k);
+    }
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::Write;
+
+/// Whether `debug!` prints, enabled by the `local` feature.
+///
+/// Checked by the macros instead of `cfg!` at the call site,
+/// which would look at the features of the caller's crate.
+pub const ENABLED: bool = cfg!(feature = "local");
+
+thread_local! {
+    static CAPTURE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Print a debug line to stderr, or into the buffer of an enclosing [`capture`].
+pub fn emit(file: &str, line: u32, body: &str) {
+    let captured = CAPTURE.with(|c| match c.borrow_mut().as_mut() {
+        Some(buf) => {
+            buf.push_str(&format!("[{}:{}] {}\n", file, line, body));
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        let stderr = std::io::stderr();
+        let mut stderr = stderr.lock();
+        let _ = writeln!(stderr, "\x1b[36m[{}:{}]\x1b[0m {}", file, line, body);
+    }
+}
+
+/// Run `f` and return what `debug!` emitted during it, for tests.
+pub fn capture<F: FnOnce()>(f: F) -> String {
+    let prev = CAPTURE.with(|c| c.borrow_mut().replace(String::new()));
+    f();
+    CAPTURE.with(|c| std::mem::replace(&mut *c.borrow_mut(), prev).unwrap_or_default())
+}
+
+/// Render rows on separate lines with right aligned cells.
+pub fn format_grid<T: Display>(grid: &[Vec<T>]) -> String {
+    let cells = grid
+        .iter()
+        .map(|row| row.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = cells.iter().flatten().map(|s| s.len()).max().unwrap_or(0);
+    cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|s| format!("{:>w$}", s, w = width))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `dbg!` like output to stderr only when the `local` feature is enabled.
+///
+/// ```
+/// # use atcoder_lib::debug;
+/// let (a, b) = (1, vec![2, 3]);
+/// debug!(a, b);                  // [src/main.rs:3] a = 1, b = [2, 3]
+/// debug!("a is {}", a);          // [src/main.rs:4] a is 1
+/// debug!(a, b; "after step {}", 2);
+/// ```
+///
+/// Without the feature the arguments are not evaluated and the call compiles to nothing.
+#[macro_export]
+macro_rules! debug {
+    ($($e:expr),+ ; $($fmt:tt)+) => {
+        if $crate::util::debug::ENABLED {
+            let exprs = [$(format!("{} = {:?}", stringify!($e), &$e)),+].join(", ");
+            $crate::util::debug::emit(
+                file!(),
+                line!(),
+                &format!("{}, {}", exprs, format!($($fmt)+)),
+            );
+        }
+    };
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {
+        if $crate::util::debug::ENABLED {
+            $crate::util::debug::emit(file!(), line!(), &format!($fmt $(, $arg)*));
+        }
+    };
+    ($($e:expr),+ $(,)?) => {
+        if $crate::util::debug::ENABLED {
+            let exprs = [$(format!("{} = {:?}", stringify!($e), &$e)),+].join(", ");
+            $crate::util::debug::emit(file!(), line!(), &exprs);
+        }
+    };
+}
+
+/// Print a `Vec<Vec<T>>` row by row, like [`debug!`] only with the `local` feature.
+#[macro_export]
+macro_rules! debug_grid {
+    ($grid:expr) => {
+        if $crate::util::debug::ENABLED {
+            $crate::util::debug::emit(
+                file!(),
+                line!(),
+                &format!(
+                    "{} =\n{}",
+                    stringify!($grid),
+                    $crate::util::debug::format_grid(&$grid)
+                ),
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_grid_aligned() {
+        let grid = vec![vec![1, 20], vec![300, 4]];
+        assert_eq!(format_grid(&grid), "  1  20\n300   4");
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn debug_enabled() {
+        let (a, b) = (1, vec!['x']);
+        let grid = vec![vec!['#', '.']];
+        let out = capture(|| {
+            crate::debug!(a, b);
+            crate::debug!("a + 1 = {}", a + 1);
+            crate::debug!(a; "step {}", 3);
+            crate::debug_grid!(grid);
+        });
+        let lines = out.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("[src/util/debug.rs:"));
+        assert!(lines[0].ends_with("] a = 1, b = ['x']"));
+        assert!(lines[1].ends_with("] a + 1 = 2"));
+        assert!(lines[2].ends_with("] a = 1, step 3"));
+        assert!(lines[3].ends_with("] grid ="));
+        assert_eq!(lines[4], "# .");
+    }
+
+    #[cfg(not(feature = "local"))]
+    #[test]
+    fn debug_disabled() {
+        let mut evaluated = false;
+        let grid = vec![vec![1]];
+        let out = capture(|| {
+            crate::debug!({
+                evaluated = true;
+                1
+            });
+            crate::debug_grid!(grid);
+        });
+        assert!(!ENABLED);
+        assert!(!evaluated);
+        assert_eq!(out, "");
+    }
+}
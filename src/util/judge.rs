@@ -0,0 +1,375 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Cursor};
+use std::path::{Path, PathBuf};
+
+use crate::contest::cio::Scanner;
+
+/// First differing line of a failed case, 1-indexed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub line: usize,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct CaseResult {
+    pub name: String,
+    pub mismatch: Option<Mismatch>,
+}
+
+impl CaseResult {
+    pub fn passed(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub struct Summary {
+    pub cases: Vec<CaseResult>,
+}
+
+impl Summary {
+    pub fn all_passed(&self) -> bool {
+        self.cases.iter().all(CaseResult::passed)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &CaseResult> {
+        self.cases.iter().filter(|c| !c.passed())
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for case in &self.cases {
+            match &case.mismatch {
+                None => writeln!(f, "{}: ok", case.name)?,
+                Some(m) => {
+                    writeln!(f, "{}: FAILED at line {}", case.name, m.line)?;
+                    writeln!(
+                        f,
+                        "  expected: {}",
+                        m.expected.as_deref().unwrap_or("<eof>")
+                    )?;
+                    writeln!(f, "  actual:   {}", m.actual.as_deref().unwrap_or("<eof>"))?;
+                }
+            }
+        }
+        let passed = self.cases.iter().filter(|c| c.passed()).count();
+        write!(f, "{}/{} passed", passed, self.cases.len())
+    }
+}
+
+/// `f(expected, actual)` deciding whether two tokens match.
+pub type TokenMatcher = Box<dyn Fn(&str, &str) -> bool>;
+
+/// How each pair of output tokens is compared.
+pub enum Policy {
+    Exact,
+    /// Tokens parsing as floats match within `abs` absolute or `rel` relative error.
+    Float {
+        abs: f64,
+        rel: f64,
+    },
+    /// `f(expected, actual)` decides.
+    Custom(TokenMatcher),
+}
+
+impl Policy {
+    /// The usual "absolute or relative error at most 1e-6".
+    pub fn float6() -> Self {
+        Policy::Float {
+            abs: 1e-6,
+            rel: 1e-6,
+        }
+    }
+
+    fn matches(&self, expected: &str, actual: &str) -> bool {
+        match self {
+            Policy::Exact => expected == actual,
+            Policy::Float { abs, rel } => match (expected.parse::<f64>(), actual.parse::<f64>()) {
+                (Ok(e), Ok(a)) => {
+                    let diff = (e - a).abs();
+                    diff <= *abs || diff <= rel * e.abs()
+                }
+                _ => expected == actual,
+            },
+            Policy::Custom(f) => f(expected, actual),
+        }
+    }
+}
+
+/// Why two outputs differ under [`compare_tokens`], lines are 1-indexed, tokens 0-indexed.
+#[derive(Debug, PartialEq)]
+pub enum TokenMismatch {
+    Count {
+        expected: usize,
+        actual: usize,
+        /// Line of the first missing or extra token
+        line: usize,
+    },
+    Token {
+        index: usize,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for TokenMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenMismatch::Count {
+                expected,
+                actual,
+                line,
+            } => write!(
+                f,
+                "expected {} tokens but got {}, diverging at line {}",
+                expected, actual, line
+            ),
+            TokenMismatch::Token {
+                index,
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "token {} at line {}: expected {:?} but got {:?}",
+                index, line, expected, actual
+            ),
+        }
+    }
+}
+
+/// `(line, token)` for every whitespace separated token.
+fn tokens(s: &str) -> Vec<(usize, &str)> {
+    s.lines()
+        .enumerate()
+        .flat_map(|(i, line)| line.split_whitespace().map(move |t| (i + 1, t)))
+        .collect()
+}
+
+/// Compare two outputs token by token, ignoring how whitespace separates them.
+pub fn compare_tokens(expected: &str, actual: &str, policy: &Policy) -> Result<(), TokenMismatch> {
+    let (expected, actual) = (tokens(expected), tokens(actual));
+    if expected.len() != actual.len() {
+        let n = expected.len().min(actual.len());
+        let line = expected.get(n).or_else(|| actual.get(n)).unwrap().0;
+        return Err(TokenMismatch::Count {
+            expected: expected.len(),
+            actual: actual.len(),
+            line,
+        });
+    }
+    for (index, (&(line, e), &(_, a))) in expected.iter().zip(&actual).enumerate() {
+        if !policy.matches(e, a) {
+            return Err(TokenMismatch::Token {
+                index,
+                line,
+                expected: e.to_string(),
+                actual: a.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Run `solve` against every `NAME.in` in `dir` and compare with `NAME.out`.
+///
+/// Trailing whitespace of each line and trailing blank lines are ignored.
+/// The report is also printed to stderr.
+pub fn run_samples<F>(dir: &Path, solve: F) -> io::Result<Summary>
+where
+    F: Fn(&mut Scanner<Cursor<&str>>, &mut Vec<u8>),
+{
+    run(dir, solve, first_mismatch)
+}
+
+/// [`run_samples`] comparing outputs with [`compare_tokens`] under `policy`.
+pub fn run_samples_with<F>(dir: &Path, policy: &Policy, solve: F) -> io::Result<Summary>
+where
+    F: Fn(&mut Scanner<Cursor<&str>>, &mut Vec<u8>),
+{
+    run(dir, solve, |expected, actual| {
+        compare_tokens(expected, actual, policy)
+            .err()
+            .map(|m| match m {
+                TokenMismatch::Count { line, .. } => Mismatch {
+                    line,
+                    expected: Some(m.to_string()),
+                    actual: None,
+                },
+                TokenMismatch::Token {
+                    line,
+                    expected,
+                    actual,
+                    ..
+                } => Mismatch {
+                    line,
+                    expected: Some(expected),
+                    actual: Some(actual),
+                },
+            })
+    })
+}
+
+fn run<F, C>(dir: &Path, solve: F, compare: C) -> io::Result<Summary>
+where
+    F: Fn(&mut Scanner<Cursor<&str>>, &mut Vec<u8>),
+    C: Fn(&str, &str) -> Option<Mismatch>,
+{
+    let mut inputs = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    inputs.retain(|p| p.extension().map_or(false, |ext| ext == "in"));
+    inputs.sort();
+
+    let mut cases = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let expected = fs::read_to_string(input.with_extension("out"))?;
+        let input_text = fs::read_to_string(&input)?;
+
+        let mut out = Vec::new();
+        solve(&mut Scanner::from(input_text.as_str()), &mut out);
+        let actual = String::from_utf8_lossy(&out);
+
+        cases.push(CaseResult {
+            name: input.file_stem().unwrap().to_string_lossy().into_owned(),
+            mismatch: compare(&expected, &actual),
+        });
+    }
+
+    let summary = Summary { cases };
+    eprintln!("{}", summary);
+    Ok(summary)
+}
+
+fn normalized_lines(s: &str) -> Vec<&str> {
+    let mut lines = s.lines().map(str::trim_end).collect::<Vec<_>>();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+fn first_mismatch(expected: &str, actual: &str) -> Option<Mismatch> {
+    let (expected, actual) = (normalized_lines(expected), normalized_lines(actual));
+    (0..expected.len().max(actual.len())).find_map(|i| {
+        let (e, a) = (expected.get(i), actual.get(i));
+        (e != a).then(|| Mismatch {
+            line: i + 1,
+            expected: e.map(|s| s.to_string()),
+            actual: a.map(|s| s.to_string()),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn run_fixture_samples() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/judge");
+        let summary = run_samples(&dir, |scanner, out| {
+            let n = scanner.scan::<usize>();
+            let sum = scanner.scan_n::<i64>(n).into_iter().sum::<i64>();
+            writeln!(out, "{}", sum).unwrap();
+        })
+        .unwrap();
+
+        assert_eq!(summary.cases.len(), 3);
+        assert!(!summary.all_passed());
+        // trailing spaces and blank lines in 2.out are ignored
+        assert!(summary.cases[0].passed() && summary.cases[1].passed());
+
+        let failed = summary.failed().collect::<Vec<_>>();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].name, "3");
+        assert_eq!(
+            failed[0].mismatch,
+            Some(Mismatch {
+                line: 1,
+                expected: Some("11".to_string()),
+                actual: Some("10".to_string()),
+            })
+        );
+        assert!(summary.to_string().contains("3: FAILED at line 1"));
+        assert!(summary.to_string().ends_with("2/3 passed"));
+    }
+
+    #[test]
+    fn float_tolerance() {
+        let policy = Policy::float6();
+        assert!(compare_tokens("0.3333333", "0.3333334", &policy).is_ok());
+        assert!(compare_tokens("0.33333", "0.33334", &policy).is_err());
+        // relative error for large values
+        assert!(compare_tokens("1e12", "1000000000000.5", &policy).is_ok());
+        assert!(compare_tokens("1.5E-3", "0.0015", &policy).is_ok());
+        assert!(compare_tokens("1 2", "1.0 2.0", &Policy::Exact).is_err());
+    }
+
+    #[test]
+    fn mixed_output() {
+        let policy = Policy::float6();
+        let expected = "Yes\n3.1415926 2\nNo\n";
+        assert!(compare_tokens(expected, "Yes\n3.14159265 2\nNo", &policy).is_ok());
+        assert_eq!(
+            compare_tokens(expected, "Yes\n3.1415926 2\nno\n", &policy),
+            Err(TokenMismatch::Token {
+                index: 3,
+                line: 3,
+                expected: "No".to_string(),
+                actual: "no".to_string(),
+            })
+        );
+
+        let custom = Policy::Custom(Box::new(|e, a| e.eq_ignore_ascii_case(a)));
+        assert!(compare_tokens(expected, "YES 3.1415926 2 NO", &custom).is_ok());
+    }
+
+    #[test]
+    fn token_count() {
+        let m = compare_tokens("1 2\n3\n", "1 2\n", &Policy::Exact).unwrap_err();
+        assert_eq!(
+            m,
+            TokenMismatch::Count {
+                expected: 3,
+                actual: 2,
+                line: 2,
+            }
+        );
+        assert_eq!(
+            m.to_string(),
+            "expected 3 tokens but got 2, diverging at line 2"
+        );
+    }
+
+    #[test]
+    fn run_with_policy() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/judge");
+        let summary = run_samples_with(&dir, &Policy::float6(), |scanner, out| {
+            let n = scanner.scan::<usize>();
+            let sum = scanner.scan_n::<i64>(n).into_iter().sum::<i64>();
+            writeln!(out, "{:.7}", sum as f64 + 1e-7).unwrap();
+        })
+        .unwrap();
+        assert_eq!(
+            summary
+                .failed()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            ["3"]
+        );
+    }
+
+    #[test]
+    fn missing_line() {
+        let m = first_mismatch("1\n2\n", "1\n").unwrap();
+        assert_eq!(m.line, 2);
+        assert_eq!(m.actual, None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Memoize a recursive function written against a callback for its recursive calls.
+///
+/// ```
+/// # use atcoder_lib::util::memo::memoize;
+/// let mut fib = memoize(|rec, n: u64| if n < 2 { n } else { rec(n - 1) + rec(n - 2) });
+/// assert_eq!(fib(90), 2880067194370816120);
+/// ```
+pub fn memoize<A, R, F>(f: F) -> impl FnMut(A) -> R
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(&mut dyn FnMut(A) -> R, A) -> R,
+{
+    let mut cache = HashMap::new();
+    move |a| call_hashed(&f, &mut cache, a)
+}
+
+fn call_hashed<A, R, F>(f: &F, cache: &mut HashMap<A, R>, a: A) -> R
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(&mut dyn FnMut(A) -> R, A) -> R,
+{
+    if let Some(r) = cache.get(&a) {
+        return r.clone();
+    }
+    let r = f(&mut |x| call_hashed(f, cache, x), a.clone());
+    cache.insert(a, r.clone());
+    r
+}
+
+/// [`memoize`] for arguments in `0..n`, cached in a `Vec`.
+pub fn memoize_vec<R, F>(n: usize, f: F) -> impl FnMut(usize) -> R
+where
+    R: Clone,
+    F: Fn(&mut dyn FnMut(usize) -> R, usize) -> R,
+{
+    let mut cache = vec![None; n];
+    move |a| call_vec(&f, &mut cache, a)
+}
+
+fn call_vec<R, F>(f: &F, cache: &mut Vec<Option<R>>, a: usize) -> R
+where
+    R: Clone,
+    F: Fn(&mut dyn FnMut(usize) -> R, usize) -> R,
+{
+    if let Some(r) = &cache[a] {
+        return r.clone();
+    }
+    let r = f(&mut |x| call_vec(f, cache, x), a);
+    cache[a] = Some(r.clone());
+    r
+}
+
+/// Evaluate a memoized recursion with an explicit stack instead of the call stack.
+///
+/// `deps(a)` lists the arguments `a` recurses into and `eval(a, results)` combines
+/// their results in the same order. Suited to deep, e.g. linear, recursions.
+pub fn recurse_with_stack<A, R, D, E>(arg: A, deps: D, eval: E) -> R
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    D: Fn(&A) -> Vec<A>,
+    E: Fn(&A, &[R]) -> R,
+{
+    let mut cache: HashMap<A, R> = HashMap::new();
+    // (argument, its dependencies, whether dependencies were pushed)
+    let mut stack = vec![(arg.clone(), deps(&arg), false)];
+
+    while let Some((a, ds, expanded)) = stack.pop() {
+        if cache.contains_key(&a) {
+            continue;
+        }
+        if expanded {
+            let results = ds.iter().map(|d| cache[d].clone()).collect::<Vec<_>>();
+            let r = eval(&a, &results);
+            cache.insert(a, r);
+        } else {
+            let pending = ds
+                .iter()
+                .filter(|d| !cache.contains_key(d))
+                .cloned()
+                .collect::<Vec<_>>();
+            stack.push((a, ds, true));
+            for d in pending {
+                let dd = deps(&d);
+                stack.push((d, dd, false));
+            }
+        }
+    }
+
+    cache.remove(&arg).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn fibonacci_computed_once() {
+        let calls = Cell::new(0);
+        let mut fib = memoize(|rec, n: u64| {
+            calls.set(calls.get() + 1);
+            if n < 2 {
+                n
+            } else {
+                rec(n - 1) + rec(n - 2)
+            }
+        });
+
+        assert_eq!(fib(50), 12586269025);
+        assert_eq!(calls.get(), 51);
+        assert_eq!(fib(40), 102334155);
+        assert_eq!(calls.get(), 51);
+    }
+
+    #[test]
+    fn tuple_key() {
+        let mut binom = memoize(|rec, (n, k): (u64, u64)| {
+            if k == 0 || k == n {
+                1u64
+            } else {
+                rec((n - 1, k - 1)) + rec((n - 1, k))
+            }
+        });
+        assert_eq!(binom((30, 15)), 155117520);
+    }
+
+    #[test]
+    fn vec_matches_hash() {
+        // number of partitions of n into parts of size 1 and 3
+        let f = |rec: &mut dyn FnMut(usize) -> u64, n: usize| {
+            if n < 3 {
+                1
+            } else {
+                rec(n - 1) + rec(n - 3)
+            }
+        };
+        let mut hashed = memoize(f);
+        let mut vec = memoize_vec(60, f);
+        for n in 0..60 {
+            assert_eq!(hashed(n), vec(n));
+        }
+    }
+
+    #[test]
+    fn deep_linear_recursion() {
+        // sum(1..=n) as f(n) = f(n - 1) + n
+        let n = 1_000_000u64;
+        let r = recurse_with_stack(
+            n,
+            |&n| if n == 0 { vec![] } else { vec![n - 1] },
+            |&n, rs| rs.first().map_or(0, |r| r + n),
+        );
+        assert_eq!(r, n * (n + 1) / 2);
+
+        let fib = recurse_with_stack(
+            80u64,
+            |&n| if n < 2 { vec![] } else { vec![n - 1, n - 2] },
+            |&n, rs| if n < 2 { n } else { rs[0] + rs[1] },
+        );
+        assert_eq!(fib, 23416728348467685);
+    }
+}
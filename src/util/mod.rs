@@ -0,0 +1,5 @@
+pub mod debug;
+pub mod judge;
+pub mod memo;
+pub mod rand;
+pub mod timer;
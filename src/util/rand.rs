@@ -0,0 +1,82 @@
+use std::ops::Range;
+
+/// xorshift64 pseudo random generator, fast and good enough for tests and heuristics.
+#[derive(Clone, Debug)]
+pub struct XorShift64(u64);
+
+impl Default for XorShift64 {
+    fn default() -> Self {
+        Self::new(88172645463325252)
+    }
+}
+
+impl XorShift64 {
+    /// A zero seed, which would only ever produce zeros, is replaced by the default one.
+    pub fn new(seed: u64) -> Self {
+        if seed == 0 {
+            Self::default()
+        } else {
+            Self(seed)
+        }
+    }
+
+    /// Seeded from the current time, for runs that should differ.
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Self::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in `range`, ignoring the negligible modulo bias.
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "empty range");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Uniform in `0..n`.
+    pub fn gen_index(&mut self, n: usize) -> usize {
+        self.gen_range(0..n as u64) as usize
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Fisher–Yates shuffle.
+    pub fn shuffle<T>(&mut self, v: &mut [T]) {
+        for i in (1..v.len()).rev() {
+            v.swap(i, self.gen_index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_and_in_range() {
+        let (mut a, mut b) = (XorShift64::new(42), XorShift64::new(42));
+        for _ in 0..1000 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        for _ in 0..1000 {
+            assert!((10..20).contains(&a.gen_range(10..20)));
+            assert!((0.0..1.0).contains(&a.next_f64()));
+        }
+
+        let mut v = (0..10).collect::<Vec<_>>();
+        a.shuffle(&mut v);
+        v.sort();
+        assert_eq!(v, (0..10).collect::<Vec<_>>());
+    }
+}
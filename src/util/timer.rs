@@ -0,0 +1,123 @@
+use std::time::{Duration, Instant};
+
+const DEFAULT_CHECK_INTERVAL: u32 = 64;
+
+/// Deadline for "run until the time limit" loops.
+///
+/// [`Timer::expired`] reads the clock only once every `check_interval` calls
+/// because `Instant::now` is measurably slow in hot loops.
+pub struct Timer {
+    start: Instant,
+    limit: Duration,
+    check_interval: u32,
+    calls: u32,
+    last_elapsed: Duration,
+    expired: bool,
+    clock_reads: u64,
+}
+
+impl Timer {
+    pub fn new(limit: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            limit,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            calls: 0,
+            last_elapsed: Duration::ZERO,
+            expired: false,
+            clock_reads: 0,
+        }
+    }
+
+    /// Read the clock in [`Timer::expired`] every `n` calls.
+    pub fn with_check_interval(mut self, n: u32) -> Self {
+        assert!(n > 0);
+        self.check_interval = n;
+        self
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.limit.saturating_sub(self.elapsed())
+    }
+
+    /// Elapsed fraction of the limit in `[0, 1]`.
+    pub fn progress(&self) -> f64 {
+        ratio(self.elapsed(), self.limit)
+    }
+
+    /// Returns `true` once the limit has passed, possibly up to `check_interval` calls late.
+    pub fn expired(&mut self) -> bool {
+        if self.expired {
+            return true;
+        }
+        self.calls += 1;
+        if self.calls >= self.check_interval {
+            self.calls = 0;
+            self.clock_reads += 1;
+            self.last_elapsed = self.start.elapsed();
+            self.expired = self.last_elapsed >= self.limit;
+        }
+        self.expired
+    }
+
+    /// Call `f` with the progress as of the last clock read until the limit passes.
+    pub fn loop_until<F: FnMut(f64)>(&mut self, mut f: F) {
+        while !self.expired() {
+            f(ratio(self.last_elapsed, self.limit));
+        }
+    }
+}
+
+fn ratio(elapsed: Duration, limit: Duration) -> f64 {
+    if limit.is_zero() {
+        return 1.0;
+    }
+    (elapsed.as_secs_f64() / limit.as_secs_f64()).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expires_after_limit() {
+        let mut timer = Timer::new(Duration::from_millis(20)).with_check_interval(1);
+        assert!(!timer.expired());
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(timer.expired());
+        assert_eq!(timer.remaining(), Duration::ZERO);
+        assert_eq!(timer.progress(), 1.0);
+    }
+
+    #[test]
+    fn loop_until_progress_is_monotone() {
+        let mut timer = Timer::new(Duration::from_millis(20));
+        let mut progress = vec![];
+        timer.loop_until(|p| progress.push(p));
+
+        assert!(!progress.is_empty());
+        assert!(progress.windows(2).all(|w| w[0] <= w[1]));
+        assert!(progress.iter().all(|p| (0.0..=1.0).contains(p)));
+        assert!(timer.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn check_throttled() {
+        let mut timer = Timer::new(Duration::from_secs(60)).with_check_interval(10);
+        for _ in 0..95 {
+            assert!(!timer.expired());
+        }
+        assert_eq!(timer.clock_reads, 9);
+
+        let mut timer = Timer::new(Duration::ZERO).with_check_interval(10);
+        for _ in 0..9 {
+            assert!(!timer.expired());
+        }
+        assert!(timer.expired());
+        assert_eq!(timer.clock_reads, 1);
+    }
+}
@@ -0,0 +1,50 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn bundle_compiles_and_runs() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO_BIN_EXE_bundle"))
+        .arg(root.join("tests/fixtures/bundle_solution.rs"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let bundled = String::from_utf8(output.stdout).unwrap();
+
+    assert!(bundled.contains("pub mod unionfind {"));
+    assert!(bundled.contains("pub mod cio {"));
+    assert!(!bundled.contains("pub mod segment_tree {"));
+    assert!(!bundled.contains("#[cfg(test)]"));
+    assert!(!bundled.contains("///"));
+
+    let dir = std::env::temp_dir().join(format!("atcoder-lib-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let main = dir.join("main.rs");
+    std::fs::write(&main, &bundled).unwrap();
+
+    let status = Command::new("rustc")
+        .current_dir(root)
+        .args(["--edition", "2018", "-o"])
+        .arg(dir.join("main"))
+        .arg(&main)
+        .status()
+        .unwrap();
+    assert!(status.success(), "bundled output failed to compile");
+
+    let mut child = Command::new(dir.join("main"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"4 5\n0 0 1\n1 0 1\n1 1 2\n0 1 2\n1 0 2\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Yes\nNo\nYes\n");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
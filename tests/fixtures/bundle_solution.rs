@@ -0,0 +1,17 @@
+use atcoder_lib::collections::unionfind::UnionFind;
+use atcoder_lib::setup;
+
+fn main() {
+    setup!(scanner);
+
+    let (n, q) = scanner.scan::<(usize, usize)>();
+    let mut uf = UnionFind::new(n);
+    for _ in 0..q {
+        let (t, u, v) = scanner.scan::<(u8, usize, usize)>();
+        if t == 0 {
+            uf.union(u, v);
+        } else {
+            println!("{}", if uf.equiv(u, v) { "Yes" } else { "No" });
+        }
+    }
+}
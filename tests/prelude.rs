@@ -0,0 +1,42 @@
+use atcoder_lib::prelude::*;
+
+/// Number of connected components and the gcd of their sizes.
+fn solve<R: std::io::BufRead>(scanner: &mut Scanner<R>) -> (usize, usize) {
+    let (n, m) = scanner.scan::<(usize, usize)>();
+    let mut uf = UnionFind::new(n);
+    for (u, v) in scanner.scan_n::<(usize, usize)>(m) {
+        uf.union(u, v);
+    }
+
+    let mut roots = (0..n)
+        .filter(|&v| (0..v).all(|u| !uf.equiv(u, v)))
+        .collect::<Vec<_>>();
+    let sizes = roots
+        .iter_mut()
+        .map(|&mut r| (0..n).filter(|&v| uf.equiv(r, v)).count())
+        .collect::<Vec<_>>();
+    (
+        sizes.len(),
+        sizes
+            .into_iter()
+            .fold(0, |g, s| if g == 0 { s } else { gcd(g, s) }),
+    )
+}
+
+#[allow(dead_code)]
+fn main_with_setup() {
+    setup!(scanner, writer);
+    writer.writeln(solve(&mut scanner).0);
+}
+
+#[test]
+fn prelude_toy_problem() {
+    let mut scanner = Scanner::from("6 4\n0 1\n1 2\n3 4\n4 5\n");
+    assert_eq!(solve(&mut scanner), (2, 3));
+
+    let t = SegmentTree::from_vec(vec![3, 1, 2], 0, |a, b| a + b);
+    assert_eq!(t.query(0..3), 6);
+    let mut g = Dag::new(2);
+    g.add_edge(0, 1, 1);
+    assert_eq!(g.dijkstra(0, 1), Some(1));
+}